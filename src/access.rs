@@ -0,0 +1,81 @@
+use std::any::TypeId;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::{Entry, StaticTypeMap};
+
+/// Reference point of recorded access times.
+static EPOCH: Lazy<Instant> = Lazy::new(Instant::now);
+
+impl<T: 'static> Entry<T> {
+    /// Record that value was accessed now.
+    pub(crate) fn record_access(&self) {
+        // Zero means never accessed, so time is stored shifted by one
+        let nanos = EPOCH.elapsed().as_nanos() as u64 + 1;
+        self.last_access.store(nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn last_access(&self) -> Option<Instant> {
+        match self.last_access.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(*EPOCH + Duration::from_nanos(nanos - 1)),
+        }
+    }
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Time value corresponding to provided type was last returned by the
+    /// map, including its initialization.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::time::Instant;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// assert_eq!(MAP.last_access::<u8>(), None);
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// assert!(MAP.last_access::<u8>().unwrap() <= Instant::now());
+    /// ```
+    pub fn last_access<Type>(&self) -> Option<Instant>
+    where
+        Type: 'static,
+    {
+        self.owned_entry(TypeId::of::<Type>())?.last_access()
+    }
+
+    /// Names of key types, whose values were not accessed since `instant`.
+    ///
+    /// Called with time warmup finished, lists values that were initialized
+    /// during warmup but never used afterwards.
+    pub fn idle_since(&self, instant: Instant) -> Vec<&'static str> {
+        let mut names: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.last_access().is_some_and(|last| last < instant))
+            .map(|entry| entry.type_name())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_not_used_after_warmup_are_idle() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| 8);
+        MAP.call_once::<u16, _>(|| 16);
+        std::thread::sleep(Duration::from_millis(1));
+        let warmed_up = Instant::now();
+
+        MAP.call_once::<u16, _>(|| unreachable!());
+        assert_eq!(MAP.idle_since(warmed_up), ["u8"]);
+    }
+}
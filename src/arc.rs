@@ -0,0 +1,225 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::OnceCell;
+
+/// Storage of value corresponding to single type.
+struct Slot<T> {
+    cell: Arc<OnceCell<Arc<T>>>,
+    generation: u64,
+}
+
+impl<T> Clone for Slot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cell: self.cell.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+/// Map of values, one per key type, owned by the map instead of leaked.
+///
+/// Counterpart of [`StaticTypeMap`](crate::StaticTypeMap) for cases where
+/// leaking is not acceptable: values are reference counted and freed when
+/// both map and all handed out `Arc`s are dropped. Map does not need to be
+/// `'static`, at the cost of reference counting on every access.
+///
+/// Values can be replaced or removed. Every value is tagged with generation,
+/// which increases each time value of the type is replaced, so holders of
+/// derived data can detect that it is stale.
+///
+/// ```
+/// use generic_static::ArcTypeMap;
+/// use std::sync::Arc;
+///
+/// let map = ArcTypeMap::new();
+/// let value = map.call_once::<u8, _>(|| "u8".to_string());
+/// assert!(Arc::ptr_eq(&value, &map.call_once::<u8, _>(|| unreachable!())));
+///
+/// drop(map);
+/// assert_eq!(Arc::strong_count(&value), 1);
+/// ```
+pub struct ArcTypeMap<T> {
+    map: RwLock<HashMap<TypeId, Slot<T>>>,
+    next_generation: AtomicU64,
+}
+
+impl<T> ArcTypeMap<T> {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+            next_generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Get value corresponding to provided type, initializing it if needed.
+    ///
+    /// Same as with `StaticTypeMap`, initializer does not hold any lock of
+    /// the map, and concurrent calls for the same type wait for it.
+    pub fn call_once<Type, Init>(&self, f: Init) -> Arc<T>
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        self.slot(TypeId::of::<Type>())
+            .cell
+            .get_or_init(|| Arc::new(f()))
+            .clone()
+    }
+
+    /// Get value corresponding to provided type, if it is initialized.
+    pub fn get<Type>(&self) -> Option<Arc<T>>
+    where
+        Type: 'static,
+    {
+        self.get_with_generation::<Type>().map(|(value, _)| value)
+    }
+
+    /// Get value corresponding to provided type along with its generation,
+    /// if it is initialized.
+    ///
+    /// ```
+    /// use generic_static::ArcTypeMap;
+    ///
+    /// let map = ArcTypeMap::new();
+    /// map.call_once::<u8, _>(|| 1);
+    /// let (_, generation) = map.get_with_generation::<u8>().unwrap();
+    ///
+    /// map.replace::<u8>(2);
+    /// assert!(map.generation::<u8>().unwrap() > generation);
+    /// ```
+    pub fn get_with_generation<Type>(&self) -> Option<(Arc<T>, u64)>
+    where
+        Type: 'static,
+    {
+        let reader = self.map.read().unwrap();
+        let slot = reader.get(&TypeId::of::<Type>())?;
+        Some((slot.cell.get()?.clone(), slot.generation))
+    }
+
+    /// Generation of value corresponding to provided type.
+    pub fn generation<Type>(&self) -> Option<u64>
+    where
+        Type: 'static,
+    {
+        self.get_with_generation::<Type>()
+            .map(|(_, generation)| generation)
+    }
+
+    /// Remove value corresponding to provided type, so next access
+    /// initializes it again. Already handed out values stay valid.
+    pub fn remove<Type>(&self) -> Option<Arc<T>>
+    where
+        Type: 'static,
+    {
+        let slot = self.map.write().unwrap().remove(&TypeId::of::<Type>())?;
+        slot.cell.get().cloned()
+    }
+
+    /// Replace value corresponding to provided type, returning previous
+    /// value. Already handed out values stay valid, subsequent accesses
+    /// return new value.
+    pub fn replace<Type>(&self, value: T) -> Option<Arc<T>>
+    where
+        Type: 'static,
+    {
+        let slot = self.new_slot();
+        let _ = slot.cell.set(Arc::new(value));
+        let previous = self
+            .map
+            .write()
+            .unwrap()
+            .insert(TypeId::of::<Type>(), slot)?;
+        previous.cell.get().cloned()
+    }
+
+    /// Remove values, for which `predicate` returns `true` and which are not
+    /// referenced outside of the map. Returns number of removed values.
+    ///
+    /// Allows to release memory under pressure while keeping values in use.
+    /// Removed types are initialized again on next access. `predicate` is
+    /// called while holding write lock of the map, so it must not access the
+    /// map.
+    ///
+    /// ```
+    /// use generic_static::ArcTypeMap;
+    /// use std::any::TypeId;
+    ///
+    /// let map = ArcTypeMap::new();
+    /// let held = map.call_once::<u8, _>(|| vec![0u8; 1024]);
+    /// map.call_once::<u16, _>(|| vec![0u8; 1024]);
+    /// map.call_once::<u32, _>(|| vec![0u8; 16]);
+    ///
+    /// assert_eq!(map.evict_if(|_, value| value.len() > 512), 1);
+    /// assert!(map.get::<u8>().is_some());
+    /// assert!(map.get::<u16>().is_none());
+    /// # drop(held);
+    /// ```
+    pub fn evict_if<P>(&self, mut predicate: P) -> usize
+    where
+        P: FnMut(TypeId, &T) -> bool,
+    {
+        let mut writer = self.map.write().unwrap();
+        let before = writer.len();
+        writer.retain(|id, slot| match slot.cell.get() {
+            Some(value) => Arc::strong_count(value) > 1 || !predicate(*id, value),
+            None => true,
+        });
+        before - writer.len()
+    }
+
+    /// Number of types with entries in the map.
+    pub fn len(&self) -> usize {
+        self.map.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn slot(&self, id: TypeId) -> Slot<T> {
+        if let Some(slot) = self.map.read().unwrap().get(&id) {
+            return slot.clone();
+        }
+        let mut writer = self.map.write().unwrap();
+        if let Some(slot) = writer.get(&id) {
+            return slot.clone();
+        }
+        let slot = self.new_slot();
+        writer.insert(id, slot.clone());
+        slot
+    }
+
+    fn new_slot(&self) -> Slot<T> {
+        Slot {
+            cell: Arc::new(OnceCell::new()),
+            generation: self.next_generation.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+impl<T> Default for ArcTypeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_value_is_reinitialized() {
+        let map = ArcTypeMap::new();
+        let first = map.call_once::<u8, _>(|| 1);
+        assert_eq!(map.get::<u16>(), None);
+
+        assert_eq!(map.remove::<u8>(), Some(first.clone()));
+        assert!(map.is_empty());
+        assert_eq!(*map.call_once::<u8, _>(|| 2), 2);
+        assert_eq!(*first, 1);
+    }
+}
@@ -0,0 +1,148 @@
+use std::any::TypeId;
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+
+use crate::{Global, StaticTypeMap};
+
+/// Builder of [`StaticTypeMap`] with non-default configuration.
+///
+/// Options correspond to setters of the map, so map can be fully
+/// configured before it is first accessed.
+///
+/// ```
+/// use generic_static::StaticTypeMap;
+/// use once_cell::sync::Lazy;
+/// use std::time::Duration;
+///
+/// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| {
+///     StaticTypeMap::builder()
+///         .name("parsers")
+///         .capacity(64)
+///         .max_entries(1024, |type_name, _| panic!("too many types, last is `{}`", type_name))
+///         .watchdog(Duration::from_secs(1), |type_name, _| eprintln!("`{}` is slow", type_name))
+///         .build()
+/// });
+///
+/// MAP.call_once::<u8, _>(|| 8);
+/// assert_eq!(MAP.name(), Some("parsers"));
+/// ```
+pub struct StaticTypeMapBuilder<T: 'static, NS: 'static = Global> {
+    map: StaticTypeMap<T, NS>,
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Start building map with non-default configuration.
+    pub fn builder() -> StaticTypeMapBuilder<T, NS> {
+        StaticTypeMapBuilder { map: Self::new() }
+    }
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMapBuilder<T, NS> {
+    /// Name of the map, see [`StaticTypeMap::named`].
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.map.name = Some(name);
+        self
+    }
+
+    /// Map to fall back to, see [`StaticTypeMap::child_of`].
+    pub fn parent(mut self, parent: &'static StaticTypeMap<T, NS>) -> Self {
+        self.map.parent = Some(parent);
+        self
+    }
+
+    /// Reserve space for at least `capacity` types in lookup table.
+    pub fn capacity(self, capacity: usize) -> Self {
+        self.map.map.write().unwrap().reserve(capacity);
+        self
+    }
+
+    /// See [`StaticTypeMap::set_max_entries`].
+    pub fn max_entries<Hook>(self, max: usize, hook: Hook) -> Self
+    where
+        Hook: Fn(&'static str, usize) + Send + Sync + 'static,
+    {
+        self.map.set_max_entries(max, hook);
+        self
+    }
+
+    /// See [`StaticTypeMap::set_leak_budget`].
+    pub fn leak_budget<Hook>(self, bytes: usize, hook: Hook) -> Self
+    where
+        Hook: Fn(usize) + Send + Sync + 'static,
+    {
+        self.map.set_leak_budget(bytes, hook);
+        self
+    }
+
+    /// See [`StaticTypeMap::set_watchdog`].
+    pub fn watchdog<Hook>(self, threshold: Duration, hook: Hook) -> Self
+    where
+        Hook: Fn(&'static str, Duration) + Send + Sync + 'static,
+    {
+        self.map.set_watchdog(threshold, hook);
+        self
+    }
+
+    /// See [`StaticTypeMap::on_init`].
+    pub fn on_init<Hook>(self, hook: Hook) -> Self
+    where
+        Hook: Fn(TypeId, &T) + Send + Sync + 'static,
+    {
+        self.map.on_init(hook);
+        self
+    }
+
+    /// See [`StaticTypeMap::set_global_default`].
+    pub fn global_default(mut self, value: T) -> Self {
+        self.map.global_default = OnceCell::new();
+        let _ = self.map.global_default.set(value);
+        self
+    }
+
+    /// Build map already sealed, see [`StaticTypeMap::seal`].
+    ///
+    /// Sealed map never initializes values itself, so it is useful as a
+    /// child, which only exposes values of its parent.
+    pub fn sealed(self) -> Self {
+        self.map.seal();
+        self
+    }
+
+    pub fn build(self) -> StaticTypeMap<T, NS> {
+        self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn built_map_has_configuration_applied() {
+        static PARENT: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| {
+            StaticTypeMap::builder()
+                .name("built")
+                .parent(&PARENT)
+                .global_default(7)
+                .build()
+        });
+
+        assert_eq!(MAP.name(), Some("built"));
+        assert!(std::ptr::eq(MAP.parent().unwrap(), &*PARENT));
+        assert_eq!(MAP.call_once_or_global::<u8>(), Some(&7));
+    }
+
+    #[test]
+    fn sealed_child_exposes_parent_values() {
+        static PARENT: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        static CHILD: Lazy<StaticTypeMap<u32>> =
+            Lazy::new(|| StaticTypeMap::builder().parent(&PARENT).sealed().build());
+
+        assert!(CHILD.is_sealed());
+        assert_eq!(*CHILD.call_once::<u8, _>(|| 8), 8);
+        assert_eq!(PARENT.try_initialized::<u8>(), Ok(&8));
+    }
+}
@@ -0,0 +1,68 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use once_cell::sync::Lazy;
+
+use crate::StaticTypeMap;
+
+/// Key of trait object of type `Type` as `Dyn`.
+struct DynKey<Type: ?Sized, Dyn: ?Sized>(PhantomData<(Box<Type>, Box<Dyn>)>);
+
+static TRAIT_OBJECTS: Lazy<StaticTypeMap<Box<dyn Any + Send + Sync>>> =
+    Lazy::new(|| StaticTypeMap::named("generic_static::cached_dyn"));
+
+/// Canonical trait object for `Type`, built by `f` on first call.
+///
+/// Every call for the same pair of `Type` and `Dyn` returns the same
+/// reference. Since unsized coercion can not be expressed generically,
+/// initializer performs it by returning `Box<Dyn>`.
+///
+/// ```
+/// use generic_static::cached_dyn;
+///
+/// trait Handler: Send + Sync {
+///     fn name(&self) -> &'static str;
+/// }
+///
+/// #[derive(Default)]
+/// struct Json;
+/// impl Handler for Json {
+///     fn name(&self) -> &'static str {
+///         "json"
+///     }
+/// }
+///
+/// fn handler<T: Handler + Default + 'static>() -> &'static dyn Handler {
+///     cached_dyn::<T, dyn Handler, _>(|| Box::new(T::default()))
+/// }
+///
+/// assert_eq!(handler::<Json>().name(), "json");
+/// assert!(std::ptr::eq(handler::<Json>(), handler::<Json>()));
+/// ```
+pub fn cached_dyn<Type, Dyn, Init>(f: Init) -> &'static Dyn
+where
+    Type: ?Sized + 'static,
+    Dyn: ?Sized + Send + Sync + 'static,
+    Init: FnOnce() -> Box<Dyn>,
+{
+    let object = TRAIT_OBJECTS.call_once::<DynKey<Type, Dyn>, _>(|| Box::new(f()));
+    // Value for the key is always initialized with `Box<Dyn>`
+    object.downcast_ref::<Box<Dyn>>().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Debug;
+
+    #[test]
+    fn different_traits_are_cached_separately() {
+        let debug = cached_dyn::<u8, dyn Debug + Send + Sync, _>(|| Box::new(1u8));
+        let any = cached_dyn::<u8, dyn Any + Send + Sync, _>(|| Box::new(2u8));
+
+        assert_eq!(format!("{:?}", debug), "1");
+        assert_eq!(any.downcast_ref::<u8>(), Some(&2));
+        let again = cached_dyn::<u8, dyn Debug + Send + Sync, _>(|| unreachable!());
+        assert!(std::ptr::eq(debug, again));
+    }
+}
@@ -0,0 +1,147 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Mutex, RwLock};
+
+struct Channel<T> {
+    /// `None` once channel was closed.
+    sender: Mutex<Option<Sender<T>>>,
+    receiver: Mutex<Option<Receiver<T>>>,
+}
+
+/// Registry of per message type channels.
+///
+/// Channel for a message type is created on first access to either of its
+/// ends, so producers and consumers can find each other by type alone.
+///
+/// Registry keeps a sender of every channel to hand out clones, so receiver
+/// never observes disconnection, and iteration over it does not end, until
+/// the channel is closed with [`close`](Self::close).
+///
+/// ```
+/// use generic_static::StaticTypeChannels;
+///
+/// struct Ping(u32);
+///
+/// let channels = StaticTypeChannels::new();
+/// channels.sender::<Ping>().send(Ping(1)).unwrap();
+///
+/// let receiver = channels.receiver::<Ping>().unwrap();
+/// assert_eq!(receiver.recv().unwrap().0, 1);
+/// ```
+pub struct StaticTypeChannels {
+    map: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl StaticTypeChannels {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Sending end of channel for provided message type.
+    ///
+    /// If channel was closed, returned sender is disconnected, so every
+    /// send fails.
+    pub fn sender<T>(&self) -> Sender<T>
+    where
+        T: Send + 'static,
+    {
+        self.with_channel(|entry: &Channel<T>| match &*entry.sender.lock().unwrap() {
+            Some(sender) => sender.clone(),
+            None => channel::<T>().0,
+        })
+    }
+
+    /// Drop sender of provided message type kept by the registry.
+    ///
+    /// Receiver is disconnected once all senders handed out before are
+    /// dropped, so that consumer loop over it ends.
+    pub fn close<T>(&self)
+    where
+        T: Send + 'static,
+    {
+        self.with_channel(|channel: &Channel<T>| channel.sender.lock().unwrap().take());
+    }
+
+    /// Receiving end of channel for provided message type.
+    ///
+    /// Channel has single consumer, so receiver is only returned by first
+    /// call. Subsequent calls return `None`.
+    pub fn receiver<T>(&self) -> Option<Receiver<T>>
+    where
+        T: Send + 'static,
+    {
+        self.with_channel(|channel: &Channel<T>| channel.receiver.lock().unwrap().take())
+    }
+
+    fn with_channel<T, R>(&self, f: impl FnOnce(&Channel<T>) -> R) -> R
+    where
+        T: Send + 'static,
+    {
+        let id = TypeId::of::<T>();
+        {
+            let reader = self.map.read().unwrap();
+            if let Some(channel) = reader.get(&id) {
+                return f(channel.downcast_ref().unwrap());
+            }
+        }
+        let mut writer = self.map.write().unwrap();
+        let channel = writer.entry(id).or_insert_with(|| {
+            let (sender, receiver) = channel::<T>();
+            Box::new(Channel {
+                sender: Mutex::new(Some(sender)),
+                receiver: Mutex::new(Some(receiver)),
+            })
+        });
+        f(channel.downcast_ref().unwrap())
+    }
+}
+
+impl Default for StaticTypeChannels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn producers_and_consumer_meet_by_type() {
+        static CHANNELS: once_cell::sync::Lazy<StaticTypeChannels> =
+            once_cell::sync::Lazy::new(StaticTypeChannels::new);
+
+        let consumer = thread::spawn(|| {
+            let receiver = CHANNELS.receiver::<u32>().unwrap();
+            receiver.iter().take(4).sum::<u32>()
+        });
+        let producers: Vec<_> = (1..=4)
+            .map(|i| thread::spawn(move || CHANNELS.sender::<u32>().send(i).unwrap()))
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        assert_eq!(consumer.join().unwrap(), 10);
+        assert!(CHANNELS.receiver::<u32>().is_none());
+        assert!(CHANNELS.receiver::<u64>().is_some());
+    }
+
+    #[test]
+    fn closed_channel_disconnects_receiver() {
+        let channels = StaticTypeChannels::new();
+        let receiver = channels.receiver::<u8>().unwrap();
+        let sender = channels.sender::<u8>();
+        sender.send(1).unwrap();
+
+        channels.close::<u8>();
+        assert!(channels.sender::<u8>().send(2).is_err());
+        sender.send(3).unwrap();
+        drop(sender);
+        assert_eq!(receiver.iter().collect::<Vec<_>>(), [1, 3]);
+    }
+}
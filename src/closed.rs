@@ -0,0 +1,100 @@
+/// Key type of table declared with [`closed_type_map!`](crate::closed_type_map).
+///
+/// Implemented by the macro for every listed type, mapping it to fixed slot
+/// of the table.
+pub trait ClosedKey<Table> {
+    const INDEX: usize;
+}
+
+/// Declare table of per-type values for set of key types known at compile
+/// time.
+///
+/// Table holds one cell per listed type, and each type is mapped to its
+/// cell at compile time, so lookups neither hash nor lock, and table never
+/// allocates. Using type, which is not listed, is a compile error. Table can
+/// be constructed in `const` context.
+///
+/// ```
+/// use generic_static::closed_type_map;
+///
+/// closed_type_map! {
+///     pub struct Sizes: usize { u8, u16, Vec<u8> }
+/// }
+///
+/// static SIZES: Sizes = Sizes::new();
+///
+/// assert_eq!(*SIZES.call_once::<u16, _>(|| 2), 2);
+/// assert_eq!(SIZES.get::<u16>(), Some(&2));
+/// assert_eq!(SIZES.get::<Vec<u8>>(), None);
+/// assert_eq!(Sizes::LEN, 3);
+/// ```
+///
+/// ```compile_fail
+/// use generic_static::closed_type_map;
+///
+/// closed_type_map! {
+///     struct Sizes: usize { u8 }
+/// }
+///
+/// Sizes::new().call_once::<u32, _>(|| 4);
+/// ```
+#[macro_export]
+macro_rules! closed_type_map {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $value:ty { $($key:ty),+ $(,)? }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            cells: [$crate::__private::OnceCell<$value>; $name::LEN],
+        }
+
+        impl $name {
+            /// Number of key types.
+            pub const LEN: usize = [$(stringify!($key)),+].len();
+
+            #[allow(clippy::declare_interior_mutable_const)]
+            pub const fn new() -> Self {
+                const EMPTY: $crate::__private::OnceCell<$value> =
+                    $crate::__private::OnceCell::new();
+                Self {
+                    cells: [EMPTY; $name::LEN],
+                }
+            }
+
+            /// Initialize value corresponding to provided type.
+            pub fn call_once<Type, Init>(&self, f: Init) -> &$value
+            where
+                Type: $crate::ClosedKey<Self>,
+                Init: FnOnce() -> $value,
+            {
+                self.cells[Type::INDEX].get_or_init(f)
+            }
+
+            /// Get value corresponding to provided type, if it is
+            /// initialized.
+            pub fn get<Type>(&self) -> Option<&$value>
+            where
+                Type: $crate::ClosedKey<Self>,
+            {
+                self.cells[Type::INDEX].get()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        $crate::closed_type_map!(@keys $name, 0, $($key),+);
+    };
+    (@keys $name:ident, $index:expr,) => {};
+    (@keys $name:ident, $index:expr, $key:ty $(, $rest:ty)*) => {
+        impl $crate::ClosedKey<$name> for $key {
+            const INDEX: usize = $index;
+        }
+
+        $crate::closed_type_map!(@keys $name, $index + 1, $($rest),*);
+    };
+}
@@ -0,0 +1,14 @@
+//! Types and traits shared by all maps, which do not depend on any storage
+//! implementation.
+
+pub use crate::closed::ClosedKey;
+pub use crate::diagnostics::NotInitialized;
+pub use crate::dispatch::BuildTable;
+pub use crate::error::Error;
+pub use crate::hash::stable_type_hash;
+#[cfg(feature = "heap-size")]
+pub use crate::memory::HeapSize;
+pub use crate::provide::ProvideValue;
+pub use crate::registry::NotRegistered;
+pub use crate::validate::ValidationError;
+pub use crate::Global;
@@ -0,0 +1,119 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Map of per type atomic counters.
+///
+/// Counters are stored directly in the map, so incrementing already
+/// existing counter only takes read lock.
+///
+/// ```
+/// use generic_static::StaticTypeCounter;
+///
+/// let counter = StaticTypeCounter::new();
+/// counter.increment::<u32>();
+/// counter.add::<u32>(2);
+/// counter.increment::<u64>();
+///
+/// assert_eq!(counter.get::<u32>(), 3);
+/// assert_eq!(counter.get::<u64>(), 1);
+/// assert_eq!(counter.get::<u8>(), 0);
+/// ```
+pub struct StaticTypeCounter {
+    map: RwLock<HashMap<TypeId, AtomicU64>>,
+}
+
+impl StaticTypeCounter {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Increment counter corresponding to provided type.
+    ///
+    /// Returns previous value of counter.
+    pub fn increment<Type>(&self) -> u64
+    where
+        Type: 'static,
+    {
+        self.add::<Type>(1)
+    }
+
+    /// Add `n` to counter corresponding to provided type.
+    ///
+    /// Returns previous value of counter.
+    pub fn add<Type>(&self, n: u64) -> u64
+    where
+        Type: 'static,
+    {
+        let id = TypeId::of::<Type>();
+        if let Some(counter) = self.map.read().unwrap().get(&id) {
+            return counter.fetch_add(n, Ordering::Relaxed);
+        }
+        self.map
+            .write()
+            .unwrap()
+            .entry(id)
+            .or_insert_with(|| AtomicU64::new(0))
+            .fetch_add(n, Ordering::Relaxed)
+    }
+
+    /// Current value of counter corresponding to provided type.
+    pub fn get<Type>(&self) -> u64
+    where
+        Type: 'static,
+    {
+        self.map
+            .read()
+            .unwrap()
+            .get(&TypeId::of::<Type>())
+            .map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+
+    /// Copy current values of all counters.
+    pub fn snapshot(&self) -> HashMap<TypeId, u64> {
+        self.map
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, counter)| (*id, counter.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+impl Default for StaticTypeCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn concurrent_increments_are_counted() {
+        let counter = Arc::new(StaticTypeCounter::new());
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let counter = counter.clone();
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        counter.increment::<u32>();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let snapshot = counter.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[&TypeId::of::<u32>()], 4000);
+    }
+}
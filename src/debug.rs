@@ -0,0 +1,51 @@
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+/// Whether logging was enabled with `GENERIC_STATIC_DEBUG=1`.
+static ENABLED: Lazy<bool> =
+    Lazy::new(|| std::env::var_os("GENERIC_STATIC_DEBUG").is_some_and(|value| value == "1"));
+
+/// Run initializer, logging it to stderr if `GENERIC_STATIC_DEBUG=1` is set.
+pub(crate) fn initialize<T, Init>(map: &str, key: &str, f: Init) -> T
+where
+    Init: FnOnce() -> T,
+{
+    if !*ENABLED {
+        return f();
+    }
+    let start = Instant::now();
+    let value = f();
+    eprintln!("{}", message(map, key, start.elapsed()));
+    value
+}
+
+fn message(map: &str, key: &str, elapsed: Duration) -> String {
+    let thread = std::thread::current();
+    format!(
+        "[generic_static] initialized `{}` in map `{}` in {:?} on thread {}",
+        key,
+        map,
+        elapsed,
+        thread.name().unwrap_or("<unnamed>")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_names_map_type_and_thread() {
+        let message = std::thread::Builder::new()
+            .name("worker".to_string())
+            .spawn(|| message("cache", "u8", Duration::from_millis(2)))
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(
+            message,
+            "[generic_static] initialized `u8` in map `cache` in 2ms on thread worker"
+        );
+    }
+}
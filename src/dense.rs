@@ -0,0 +1,157 @@
+use std::any::TypeId;
+
+use crate::StaticTypeIndex;
+
+/// Vec-backed storage of per type values, addressed by [`StaticTypeIndex`].
+///
+/// Values are registered by type, after which they can be accessed with
+/// index obtained during registration. Such lookup is a plain bounds
+/// checked array access, without any hashing or locking. Several storages
+/// may share same index, so single type index addresses all of them.
+///
+/// ```
+/// use generic_static::{DenseTypeMap, StaticTypeIndex};
+/// use once_cell::sync::Lazy;
+///
+/// static INDEX: Lazy<StaticTypeIndex> = Lazy::new(StaticTypeIndex::new);
+///
+/// let mut names = DenseTypeMap::new(&INDEX);
+/// names.insert::<u32>("u32");
+/// let index = INDEX.index_of::<u32>();
+///
+/// assert_eq!(names.get_by_index(index), Some(&"u32"));
+/// assert_eq!(names.get::<u32>(), Some(&"u32"));
+/// ```
+pub struct DenseTypeMap<T> {
+    index: &'static StaticTypeIndex,
+    values: Vec<Option<(TypeId, T)>>,
+}
+
+impl<T> DenseTypeMap<T> {
+    pub fn new(index: &'static StaticTypeIndex) -> Self {
+        Self {
+            index,
+            values: Vec::new(),
+        }
+    }
+
+    /// Index used to address values.
+    pub fn index(&self) -> &'static StaticTypeIndex {
+        self.index
+    }
+
+    /// Store value corresponding to provided type.
+    ///
+    /// Returns previously stored value, if any.
+    pub fn insert<Type>(&mut self, value: T) -> Option<T>
+    where
+        Type: 'static,
+    {
+        let index = self.index.index_of::<Type>();
+        if index >= self.values.len() {
+            self.values.resize_with(index + 1, || None);
+        }
+        self.values[index]
+            .replace((TypeId::of::<Type>(), value))
+            .map(|(_, value)| value)
+    }
+
+    /// Get value corresponding to provided type.
+    ///
+    /// Requires lookup in type index, prefer [`DenseTypeMap::get_by_index`]
+    /// on hot paths.
+    pub fn get<Type>(&self) -> Option<&T>
+    where
+        Type: 'static,
+    {
+        self.index
+            .get::<Type>()
+            .and_then(|index| self.get_by_index(index))
+    }
+
+    /// Get value by index assigned to its type.
+    pub fn get_by_index(&self, index: usize) -> Option<&T> {
+        self.values
+            .get(index)
+            .and_then(Option::as_ref)
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_by_index_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.values
+            .get_mut(index)
+            .and_then(Option::as_mut)
+            .map(|(_, value)| value)
+    }
+
+    /// Iterate over all stored values mutably.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.values
+            .iter_mut()
+            .filter_map(Option::as_mut)
+            .map(|(_, value)| value)
+    }
+
+    /// Remove all values, for which predicate returns `false`.
+    ///
+    /// Indices of retained values are not changed.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(TypeId, &T) -> bool,
+    {
+        for slot in &mut self.values {
+            if let Some((id, value)) = slot {
+                if !f(*id, value) {
+                    *slot = None;
+                }
+            }
+        }
+    }
+
+    /// Number of stored values.
+    pub fn len(&self) -> usize {
+        self.values.iter().filter(|value| value.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storages_share_index() {
+        let index = Box::leak(Box::new(StaticTypeIndex::new()));
+        let mut names = DenseTypeMap::new(index);
+        let mut sizes = DenseTypeMap::new(index);
+
+        sizes.insert::<u64>(8);
+        names.insert::<u8>("u8");
+        names.insert::<u64>("u64");
+
+        let u8_index = index.index_of::<u8>();
+        assert_eq!(names.get_by_index(u8_index), Some(&"u8"));
+        assert_eq!(sizes.get_by_index(u8_index), None);
+        assert_eq!(sizes.get::<u64>(), Some(&8));
+        assert_eq!(names.len(), 2);
+        assert_eq!(sizes.len(), 1);
+    }
+
+    #[test]
+    fn retain_keeps_indices() {
+        let index = Box::leak(Box::new(StaticTypeIndex::new()));
+        let mut values = DenseTypeMap::new(index);
+        values.insert::<u8>(1);
+        values.insert::<u16>(2);
+        values.insert::<u32>(3);
+
+        values.retain(|id, value| id != TypeId::of::<u16>() && *value < 10);
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get::<u16>(), None);
+        assert_eq!(values.get_by_index(index.index_of::<u32>()), Some(&3));
+    }
+}
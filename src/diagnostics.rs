@@ -0,0 +1,229 @@
+use std::any::{type_name, TypeId};
+use std::error::Error;
+use std::fmt;
+use std::panic::Location;
+
+use crate::StaticTypeMap;
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Find key type of value, previously returned by the map.
+    ///
+    /// Values are compared by address, so only references handed out by the
+    /// map itself can be found. Returns `TypeId` and name of key type.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::any::TypeId;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let value = MAP.call_once::<u8, _>(|| 8);
+    /// assert_eq!(MAP.key_of_value(value), Some((TypeId::of::<u8>(), "u8")));
+    /// assert_eq!(MAP.key_of_value(&8), None);
+    /// ```
+    pub fn key_of_value(&self, value: &T) -> Option<(TypeId, &'static str)> {
+        self.entries()
+            .into_iter()
+            .find(|entry| entry.cell.get().is_some_and(|v| std::ptr::eq(v, value)))
+            .map(|entry| (entry.type_id, entry.type_name()))
+    }
+
+    /// Get value corresponding to provided type, if it is initialized.
+    ///
+    /// Error lists names of types, which are initialized.
+    pub fn try_initialized<Type>(&self) -> Result<&'static T, NotInitialized>
+    where
+        Type: 'static,
+    {
+        if let Some(value) = self
+            .owned_entry(TypeId::of::<Type>())
+            .and_then(|entry| entry.cell.get())
+        {
+            return Ok(value);
+        }
+        let mut initialized: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.cell.get().is_some())
+            .map(|entry| entry.type_name())
+            .collect();
+        initialized.sort_unstable();
+        Err(NotInitialized {
+            type_name: type_name::<Type>(),
+            map: self.name,
+            value_type: type_name::<T>(),
+            initialized,
+        })
+    }
+
+    /// Get value corresponding to provided type, which must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if value is not initialized. Message lists names of types,
+    /// which are initialized.
+    ///
+    /// ```should_panic
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// assert_eq!(*MAP.assert_initialized::<u8>(), 8);
+    /// // type `u16` is not initialized in `StaticTypeMap<u32>`, initialized types: [u8]
+    /// MAP.assert_initialized::<u16>();
+    /// ```
+    #[track_caller]
+    pub fn assert_initialized<Type>(&self) -> &'static T
+    where
+        Type: 'static,
+    {
+        match self.try_initialized::<Type>() {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Same as [`assert_initialized`](Self::assert_initialized), but panic
+    /// message is prefixed with `msg`, same as with `Option::expect`.
+    ///
+    /// ```should_panic
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::named("limits"));
+    ///
+    /// // limit must be set during warmup: type `u16` is not initialized in map `limits`, ...
+    /// MAP.expect::<u16>("limit must be set during warmup");
+    /// ```
+    #[track_caller]
+    pub fn expect<Type>(&self, msg: &str) -> &'static T
+    where
+        Type: 'static,
+    {
+        match self.try_initialized::<Type>() {
+            Ok(value) => value,
+            Err(err) => panic!("{}: {}", msg, err),
+        }
+    }
+
+    /// Call site, which initialized value corresponding to provided type.
+    ///
+    /// When several call sites race to initialize the same type with
+    /// different initializers, tells which one won.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let line = line!();
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// let location = MAP.init_location::<u8>().unwrap();
+    /// assert_eq!(location.line(), line + 1);
+    /// ```
+    pub fn init_location<Type>(&self) -> Option<&'static Location<'static>>
+    where
+        Type: 'static,
+    {
+        self.owned_entry(TypeId::of::<Type>())?
+            .init_location
+            .get()
+            .copied()
+    }
+}
+
+#[cfg(feature = "init-trace")]
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Backtrace captured when value corresponding to provided type was
+    /// initialized.
+    ///
+    /// Backtrace is captured regardless of `RUST_BACKTRACE`, so enabling
+    /// `init-trace` feature is only recommended for debugging.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// assert!(MAP.init_trace::<u8>().is_none());
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// println!("u8 initialized at:\n{}", MAP.init_trace::<u8>().unwrap());
+    /// ```
+    pub fn init_trace<Type>(&self) -> Option<&'static std::backtrace::Backtrace>
+    where
+        Type: 'static,
+    {
+        self.owned_entry(TypeId::of::<Type>())?.init_trace.get()
+    }
+}
+
+/// Error returned when looking up value, which is not initialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotInitialized {
+    type_name: &'static str,
+    map: Option<&'static str>,
+    value_type: &'static str,
+    initialized: Vec<&'static str>,
+}
+
+impl NotInitialized {
+    /// Name of type, which was looked up.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Names of types initialized at the time of lookup, sorted.
+    pub fn initialized(&self) -> &[&'static str] {
+        &self.initialized
+    }
+}
+
+impl fmt::Display for NotInitialized {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "type `{}` is not initialized in ", self.type_name)?;
+        match self.map {
+            Some(name) => write!(f, "map `{}`", name)?,
+            None => write!(f, "`StaticTypeMap<{}>`", self.value_type)?,
+        }
+        write!(f, ", initialized types: [{}]", self.initialized.join(", "))
+    }
+}
+
+impl Error for NotInitialized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn missing_type_lists_initialized() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u16, _>(|| 16);
+        MAP.call_once::<u8, _>(|| 8);
+
+        let err = MAP.try_initialized::<u64>().unwrap_err();
+        assert_eq!(err.type_name(), "u64");
+        assert_eq!(err.initialized(), ["u16", "u8"]);
+        assert_eq!(
+            err.to_string(),
+            "type `u64` is not initialized in `StaticTypeMap<u32>`, initialized types: [u16, u8]"
+        );
+    }
+
+    #[test]
+    fn named_map_is_reported_by_name() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::named("numbers"));
+        MAP.call_once::<u8, _>(|| 8);
+
+        assert_eq!(
+            MAP.try_initialized::<u64>().unwrap_err().to_string(),
+            "type `u64` is not initialized in map `numbers`, initialized types: [u8]"
+        );
+    }
+}
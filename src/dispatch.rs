@@ -0,0 +1,89 @@
+use crate::StaticTypeMap;
+
+/// Tables of function pointers, which can be built for key type `Type`.
+///
+/// Implemented by table type, usually generically over all types
+/// satisfying some bounds, so each table holds functions monomorphized for
+/// its key type. See [`DispatchTables`].
+pub trait BuildTable<Type> {
+    fn build() -> Self;
+}
+
+/// Cache of per type dispatch tables.
+///
+/// ```
+/// use generic_static::{BuildTable, DispatchTables};
+/// use once_cell::sync::Lazy;
+/// use std::any::Any;
+/// use std::fmt::Debug;
+///
+/// struct DebugTable {
+///     debug: fn(&dyn Any) -> String,
+/// }
+///
+/// impl<T: Debug + 'static> BuildTable<T> for DebugTable {
+///     fn build() -> Self {
+///         DebugTable {
+///             debug: |value| format!("{:?}", value.downcast_ref::<T>().unwrap()),
+///         }
+///     }
+/// }
+///
+/// static TABLES: Lazy<DispatchTables<DebugTable>> = Lazy::new(DispatchTables::new);
+///
+/// let value: Box<dyn Any> = Box::new(Some(1u8));
+/// let table = TABLES.get::<Option<u8>>();
+/// assert_eq!((table.debug)(&*value), "Some(1)");
+/// assert!(std::ptr::eq(table, TABLES.get::<Option<u8>>()));
+/// ```
+pub struct DispatchTables<D: 'static> {
+    map: StaticTypeMap<D>,
+}
+
+impl<D: 'static> DispatchTables<D> {
+    pub fn new() -> Self {
+        Self {
+            map: StaticTypeMap::new(),
+        }
+    }
+
+    /// Dispatch table of provided type, built on first access.
+    pub fn get<Type>(&'static self) -> &'static D
+    where
+        Type: 'static,
+        D: BuildTable<Type>,
+    {
+        self.map
+            .call_once::<Type, _>(<D as BuildTable<Type>>::build)
+    }
+}
+
+impl<D: 'static> Default for DispatchTables<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::mem::size_of;
+
+    struct SizeTable {
+        size: fn() -> usize,
+    }
+
+    impl<T> BuildTable<T> for SizeTable {
+        fn build() -> Self {
+            SizeTable { size: size_of::<T> }
+        }
+    }
+
+    #[test]
+    fn tables_are_built_per_type() {
+        static TABLES: Lazy<DispatchTables<SizeTable>> = Lazy::new(DispatchTables::new);
+        assert_eq!((TABLES.get::<u16>().size)(), 2);
+        assert_eq!((TABLES.get::<u64>().size)(), 8);
+    }
+}
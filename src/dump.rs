@@ -0,0 +1,119 @@
+use std::sync::Mutex;
+
+use crate::StaticTypeMap;
+
+/// Object safe view of a map, used by process wide registry.
+trait Dump: Sync {
+    fn dump(&self) -> MapDump;
+}
+
+impl<T: Send + Sync + 'static, NS: 'static> Dump for StaticTypeMap<T, NS> {
+    fn dump(&self) -> MapDump {
+        let mut type_names: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.cell.get().is_some())
+            .map(|entry| entry.type_name())
+            .collect();
+        type_names.sort_unstable();
+        MapDump {
+            name: self.name,
+            map: std::any::type_name::<Self>(),
+            address: self as *const Self as usize,
+            type_names,
+        }
+    }
+}
+
+static REGISTRY: Mutex<Vec<&'static dyn Dump>> = Mutex::new(Vec::new());
+
+/// Summary of single map, see [`dump_all`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapDump {
+    /// Name given with [`StaticTypeMap::named`].
+    pub name: Option<&'static str>,
+    /// Type name of the map.
+    pub map: &'static str,
+    pub address: usize,
+    /// Names of initialized key types, sorted.
+    pub type_names: Vec<&'static str>,
+}
+
+impl MapDump {
+    /// Number of initialized values.
+    pub fn len(&self) -> usize {
+        self.type_names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.type_names.is_empty()
+    }
+}
+
+impl<T, NS: 'static> StaticTypeMap<T, NS>
+where
+    T: Send + Sync + 'static,
+{
+    /// Add map to process wide registry, enumerated by [`dump_all`].
+    ///
+    /// Registering same map more than once has no effect.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.register_global();
+    /// MAP.call_once::<u8, _>(|| 8);
+    ///
+    /// let dump = generic_static::dump_all();
+    /// assert!(dump.iter().any(|map| map.type_names == ["u8"]));
+    /// ```
+    pub fn register_global(&'static self) {
+        let mut registry = REGISTRY.lock().unwrap();
+        let address = self as *const Self as usize;
+        let registered = registry
+            .iter()
+            .any(|map| *map as *const dyn Dump as *const () as usize == address);
+        if !registered {
+            registry.push(self);
+        }
+    }
+}
+
+/// Summarize all maps added with
+/// [`register_global`](StaticTypeMap::register_global), in order of
+/// registration.
+pub fn dump_all() -> Vec<MapDump> {
+    let maps: Vec<_> = REGISTRY.lock().unwrap().clone();
+    maps.into_iter().map(|map| map.dump()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn registered_maps_are_dumped_once() {
+        static NUMBERS: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        static NAMES: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        NUMBERS.register_global();
+        NAMES.register_global();
+        NUMBERS.register_global();
+        NAMES.call_once::<u16, _>(String::new);
+        NAMES.call_once::<u8, _>(String::new);
+
+        let address = &*NAMES as *const StaticTypeMap<String> as usize;
+        let dump = dump_all();
+        let names: Vec<_> = dump.iter().filter(|map| map.address == address).collect();
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[0].type_names, ["u16", "u8"]);
+        // Exact output of `type_name` is not stable
+        assert!(names[0].map.contains("StaticTypeMap"));
+        assert!(names[0].map.contains("String"));
+        let numbers = &*NUMBERS as *const StaticTypeMap<u32> as usize;
+        assert_eq!(dump.iter().filter(|map| map.address == numbers).count(), 1);
+    }
+}
@@ -0,0 +1,103 @@
+use std::any::TypeId;
+
+use crate::{Global, StaticTypeMap, UNKNOWN_TYPE_NAME};
+
+/// Object safe interface of static type map.
+///
+/// Unlike methods of [`StaticTypeMap`], methods of this trait are not
+/// generic, so it can be used as trait object.
+pub trait DynTypeMap<T: 'static> {
+    /// Get initialized value corresponding to type with provided `TypeId`.
+    fn lookup(&self, id: TypeId) -> Option<&'static T>;
+
+    /// Initialize value corresponding to type with provided `TypeId`, see
+    /// [`StaticTypeMap::get_or_init_dyn`].
+    fn get_or_init_dyn(&self, id: TypeId, f: &dyn Fn() -> T) -> &'static T;
+
+    /// Initialize value corresponding to type with provided `TypeId` with
+    /// `value`.
+    ///
+    /// If value was already initialized, `value` is dropped and existing
+    /// value is returned.
+    fn insert_boxed(&self, id: TypeId, value: Box<T>) -> &'static T;
+}
+
+/// Wrapper of static reference to [`StaticTypeMap`], implementing
+/// [`DynTypeMap`].
+///
+/// ```
+/// use generic_static::{DynStaticMap, DynTypeMap, StaticTypeMap};
+/// use once_cell::sync::Lazy;
+/// use std::any::TypeId;
+///
+/// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+///
+/// let map: Box<dyn DynTypeMap<String>> = Box::new(DynStaticMap::new(&MAP));
+/// map.insert_boxed(TypeId::of::<u8>(), Box::new("u8".to_string()));
+///
+/// assert_eq!(map.lookup(TypeId::of::<u8>()).unwrap(), "u8");
+/// assert_eq!(map.lookup(TypeId::of::<u16>()), None);
+/// ```
+pub struct DynStaticMap<T: 'static, NS: 'static = Global> {
+    map: &'static StaticTypeMap<T, NS>,
+}
+
+impl<T: 'static, NS: 'static> DynStaticMap<T, NS> {
+    pub fn new(map: &'static StaticTypeMap<T, NS>) -> Self {
+        Self { map }
+    }
+
+    /// Wrapped map.
+    pub fn map(&self) -> &'static StaticTypeMap<T, NS> {
+        self.map
+    }
+}
+
+impl<T: 'static, NS: 'static> Clone for DynStaticMap<T, NS> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, NS: 'static> Copy for DynStaticMap<T, NS> {}
+
+impl<T: 'static, NS: 'static> DynTypeMap<T> for DynStaticMap<T, NS> {
+    fn lookup(&self, id: TypeId) -> Option<&'static T> {
+        self.map.owned_entry(id).and_then(|entry| entry.cell.get())
+    }
+
+    fn get_or_init_dyn(&self, id: TypeId, f: &dyn Fn() -> T) -> &'static T {
+        self.map.get_or_init_dyn(id, f)
+    }
+
+    fn insert_boxed(&self, id: TypeId, value: Box<T>) -> &'static T {
+        let entry = self.map.entry_by_id(id, UNKNOWN_TYPE_NAME);
+        self.map.get_or_init(entry, move || *value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn type_name_is_learned_from_typed_access() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        let map = DynStaticMap::new(&MAP);
+        map.insert_boxed(TypeId::of::<u8>(), Box::new(8));
+        map.insert_boxed(TypeId::of::<u16>(), Box::new(16));
+        let names = || {
+            let mut names: Vec<_> = crate::raw::entries(&MAP)
+                .into_iter()
+                .map(|entry| entry.type_name())
+                .collect();
+            names.sort_unstable();
+            names
+        };
+        assert_eq!(names(), ["<unknown>", "<unknown>"]);
+
+        MAP.call_once::<u8, _>(|| unreachable!());
+        assert_eq!(names(), ["<unknown>", "u8"]);
+    }
+}
@@ -0,0 +1,192 @@
+/// Initializer registered at link time, see [`register_initializer!`].
+///
+/// [`register_initializer!`]: crate::register_initializer
+pub struct Initializer {
+    name: &'static str,
+    dependencies: &'static [&'static str],
+    init: fn(),
+}
+
+impl Initializer {
+    pub const fn new(name: &'static str, init: fn()) -> Self {
+        Self::with_dependencies(name, &[], init)
+    }
+
+    /// Initializer, which must run after initializers with provided names.
+    pub const fn with_dependencies(
+        name: &'static str,
+        dependencies: &'static [&'static str],
+        init: fn(),
+    ) -> Self {
+        Self {
+            name,
+            dependencies,
+            init,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Names of initializers, which must run before this one.
+    pub fn dependencies(&self) -> &'static [&'static str] {
+        self.dependencies
+    }
+
+    pub fn run(&self) {
+        (self.init)()
+    }
+}
+
+inventory::collect!(Initializer);
+
+/// Iterate over all initializers registered with [`register_initializer!`].
+///
+/// [`register_initializer!`]: crate::register_initializer
+pub fn initializers() -> impl Iterator<Item = &'static Initializer> {
+    inventory::iter::<Initializer>.into_iter()
+}
+
+/// Eagerly run all initializers registered with [`register_initializer!`].
+///
+/// Useful to pay for per type initialization at startup instead of at first
+/// access. Initializers are expected to only call `call_once` and similar
+/// methods, so running them more than once is harmless.
+///
+/// Initializers run after all of their declared dependencies.
+///
+/// # Panics
+///
+/// Panics if dependencies form a cycle.
+///
+/// [`register_initializer!`]: crate::register_initializer
+pub fn init_all() {
+    for level in schedule(initializers().collect()) {
+        for initializer in level {
+            initializer.run();
+        }
+    }
+}
+
+/// Same as [`init_all`], but runs initializers in parallel on rayon global
+/// thread pool.
+///
+/// Initializers are grouped into levels, with every initializer depending
+/// only on initializers from previous levels. Initializers of the same level
+/// run in parallel.
+#[cfg(feature = "rayon")]
+pub fn par_init_all() {
+    use rayon::prelude::*;
+
+    for level in schedule(initializers().collect()) {
+        level
+            .into_par_iter()
+            .for_each(|initializer| initializer.run());
+    }
+}
+
+/// Split initializers into levels in dependency order. Dependencies on
+/// names, which are not registered, are ignored.
+fn schedule(initializers: Vec<&'static Initializer>) -> Vec<Vec<&'static Initializer>> {
+    let mut remaining = initializers;
+    let mut levels = Vec::new();
+    while !remaining.is_empty() {
+        let names: Vec<_> = remaining
+            .iter()
+            .map(|initializer| initializer.name)
+            .collect();
+        let (ready, blocked): (Vec<&'static Initializer>, Vec<_>) =
+            remaining.into_iter().partition(|initializer| {
+                initializer
+                    .dependencies
+                    .iter()
+                    .all(|dep| !names.contains(dep))
+            });
+        if ready.is_empty() {
+            panic!("initializer dependency cycle among {:?}", names);
+        }
+        levels.push(ready);
+        remaining = blocked;
+    }
+    levels
+}
+
+/// Register initializer at link time, to be run by [`init_all`].
+///
+/// Accepts function (or non capturing closure) taking no arguments,
+/// optionally preceded by name used in diagnostics and list of names of
+/// initializers it depends on.
+///
+/// ```
+/// use generic_static::{register_initializer, StaticTypeMap};
+/// use once_cell::sync::Lazy;
+///
+/// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+///
+/// fn name<T: 'static>() -> &'static str {
+///     MAP.call_once::<T, _>(|| std::any::type_name::<T>().to_string())
+/// }
+///
+/// register_initializer!(|| {
+///     name::<u32>();
+/// });
+/// register_initializer!("u64 name" => || {
+///     name::<u64>();
+/// });
+/// register_initializer!("both names", after ["u64 name"] => || {
+///     name::<(u32, u64)>();
+/// });
+///
+/// generic_static::init_all();
+/// assert_eq!(MAP.stats().entries.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! register_initializer {
+    ($name:expr, after [$($dep:expr),* $(,)?] => $init:expr) => {
+        $crate::__private::inventory::submit! {
+            $crate::Initializer::with_dependencies($name, &[$($dep),*], $init)
+        }
+    };
+    ($name:expr => $init:expr) => {
+        $crate::__private::inventory::submit! {
+            $crate::Initializer::new($name, $init)
+        }
+    };
+    ($init:expr) => {
+        $crate::register_initializer!(::core::stringify!($init) => $init);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static A: Initializer = Initializer::new("a", || {});
+    static B: Initializer = Initializer::with_dependencies("b", &["a", "missing"], || {});
+    static C: Initializer = Initializer::with_dependencies("c", &["a", "b"], || {});
+    static D: Initializer = Initializer::new("d", || {});
+
+    fn names(levels: Vec<Vec<&'static Initializer>>) -> Vec<Vec<&'static str>> {
+        levels
+            .into_iter()
+            .map(|level| level.into_iter().map(Initializer::name).collect())
+            .collect()
+    }
+
+    #[test]
+    fn dependencies_run_first() {
+        assert_eq!(
+            names(schedule(vec![&C, &B, &A, &D])),
+            vec![vec!["a", "d"], vec!["b"], vec!["c"]]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "initializer dependency cycle")]
+    fn cycle_panics() {
+        static X: Initializer = Initializer::with_dependencies("x", &["y"], || {});
+        static Y: Initializer = Initializer::with_dependencies("y", &["x"], || {});
+        schedule(vec![&X, &Y]);
+    }
+}
@@ -0,0 +1,112 @@
+use std::any::TypeId;
+use std::fmt;
+use std::ops::Deref;
+use std::time::Duration;
+
+use crate::{Entry, StaticTypeMap};
+
+/// Reference to static value, carrying metadata of its entry.
+///
+/// Returned by [`StaticTypeMap::call_once_entry`].
+pub struct EntryRef<T: 'static> {
+    entry: &'static Entry<T>,
+    value: &'static T,
+    initialized: bool,
+}
+
+impl<T: 'static> EntryRef<T> {
+    /// Referenced value.
+    pub fn value(&self) -> &'static T {
+        self.value
+    }
+
+    /// `TypeId` of key type.
+    pub fn type_id(&self) -> TypeId {
+        self.entry.type_id
+    }
+
+    /// Name of key type.
+    pub fn type_name(&self) -> &'static str {
+        self.entry.type_name()
+    }
+
+    /// Whether value was initialized by the call, which returned this
+    /// reference.
+    pub fn initialized_by_this_call(&self) -> bool {
+        self.initialized
+    }
+
+    /// Time spent in initializer, if value was initialized through the map.
+    pub fn init_duration(&self) -> Option<Duration> {
+        self.entry.init_duration.get().cloned()
+    }
+
+    /// Wall clock time, when initialization finished.
+    #[cfg(feature = "timestamps")]
+    pub fn init_time(&self) -> Option<std::time::SystemTime> {
+        self.entry.init_time.get().cloned()
+    }
+}
+
+impl<T: 'static> Deref for EntryRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T: 'static> Clone for EntryRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for EntryRef<T> {}
+
+impl<T: fmt::Debug + 'static> fmt::Debug for EntryRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EntryRef")
+            .field("type_name", &self.entry.type_name())
+            .field("value", self.value)
+            .field("initialized", &self.initialized)
+            .finish()
+    }
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Same as [`StaticTypeMap::call_once`], but returns handle carrying
+    /// metadata of the entry.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let first = MAP.call_once_entry::<u8, _>(|| 8);
+    /// let second = MAP.call_once_entry::<u8, _>(|| 9);
+    ///
+    /// assert_eq!(*first, 8);
+    /// assert_eq!(*second, 8);
+    /// assert_eq!(first.type_name(), "u8");
+    /// assert!(first.initialized_by_this_call());
+    /// assert!(!second.initialized_by_this_call());
+    /// assert!(second.init_duration().is_some());
+    /// ```
+    #[track_caller]
+    pub fn call_once_entry<Type, Init>(&'static self, f: Init) -> EntryRef<T>
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        let map = self.owner(TypeId::of::<Type>());
+        let entry = map.entry::<Type>();
+        let (value, initialized) = map.get_or_init_tracked(entry, f);
+        EntryRef {
+            entry,
+            value,
+            initialized,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::error;
+use std::fmt;
+
+use crate::{NotInitialized, NotRegistered};
+
+/// Error of fallible operations of the crate.
+///
+/// Returned by [`StaticTypeMap::try_call_once`](crate::StaticTypeMap::try_call_once)
+/// instead of panicking, and wraps errors of lookups which require value to
+/// be present.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Value is not initialized, see
+    /// [`StaticTypeMap::try_initialized`](crate::StaticTypeMap::try_initialized).
+    NotInitialized(NotInitialized),
+    /// Type was never registered in [`TypedRegistry`](crate::TypedRegistry).
+    NotRegistered(NotRegistered),
+    /// Previous initializer of the type panicked.
+    Poisoned { type_name: &'static str },
+    /// Initializer of the type requires its own value. Lists types being
+    /// initialized, from the requested type to the one which requested it
+    /// again.
+    Cycle { type_names: Vec<&'static str> },
+    /// Map was sealed before the type was initialized.
+    Sealed { type_name: &'static str },
+    /// Map already has maximal number of entries, see
+    /// [`StaticTypeMap::set_max_entries`](crate::StaticTypeMap::set_max_entries).
+    CapacityExceeded { type_name: &'static str, max: usize },
+    /// Another copy of the crate is linked into the process, see
+    /// [`check_unique_instance`](crate::check_unique_instance). Contains
+    /// version and address identifying the other copy. Requires
+    /// `instance-check` feature.
+    #[cfg(feature = "instance-check")]
+    DuplicateCrate { other: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NotInitialized(err) => err.fmt(f),
+            Error::NotRegistered(err) => err.fmt(f),
+            Error::Poisoned { type_name } => {
+                write!(
+                    f,
+                    "initializer of `{}` panicked, value is poisoned",
+                    type_name
+                )
+            }
+            Error::Cycle { type_names } => {
+                write!(f, "initialization cycle: {}", type_names.join(" -> "))
+            }
+            Error::Sealed { type_name } => {
+                write!(f, "initialization of `{}` in sealed map", type_name)
+            }
+            Error::CapacityExceeded { type_name, max } => write!(
+                f,
+                "can not create entry of `{}`, map is limited to {} entries",
+                type_name, max
+            ),
+            #[cfg(feature = "instance-check")]
+            Error::DuplicateCrate { other } => write!(
+                f,
+                "another copy of generic_static is linked into the process: {}",
+                other
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::NotInitialized(err) => Some(err),
+            Error::NotRegistered(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<NotInitialized> for Error {
+    fn from(err: NotInitialized) -> Self {
+        Error::NotInitialized(err)
+    }
+}
+
+impl From<NotRegistered> for Error {
+    fn from(err: NotRegistered) -> Self {
+        Error::NotRegistered(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StaticTypeMap;
+    use once_cell::sync::Lazy;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn failures_are_reported_as_errors() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.set_max_entries(2, |_, _| {});
+
+        assert!(catch_unwind(|| MAP.call_once::<u8, _>(|| panic!("failed"))).is_err());
+        let poisoned = MAP.try_call_once::<u8, _>(|| 8).unwrap_err();
+        assert_eq!(poisoned, Error::Poisoned { type_name: "u8" });
+
+        assert_eq!(MAP.try_call_once::<u16, _>(|| 16), Ok(&16));
+        let full = MAP.try_call_once::<u32, _>(|| 32).unwrap_err();
+        assert_eq!(
+            full.to_string(),
+            "can not create entry of `u32`, map is limited to 2 entries"
+        );
+    }
+}
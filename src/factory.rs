@@ -0,0 +1,87 @@
+use crate::{NotRegistered, TypedRegistry};
+
+type Factory<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+/// Registry of per type factories, building new value on every call.
+///
+/// Counterpart of [`StaticTypeMap`](crate::StaticTypeMap) for values, which
+/// must not be shared: instead of caching single value per type, registered
+/// factory is called by each [`create`](Self::create).
+///
+/// ```
+/// use generic_static::FactoryRegistry;
+///
+/// let mut factories = FactoryRegistry::new();
+/// factories.register_factory::<u32, _>(|| Vec::with_capacity(32));
+///
+/// let a: Vec<u8> = factories.create::<u32>().unwrap();
+/// let b: Vec<u8> = factories.create::<u32>().unwrap();
+/// assert_ne!(a.as_ptr(), b.as_ptr());
+/// assert!(factories.create::<u64>().is_err());
+/// ```
+pub struct FactoryRegistry<T> {
+    factories: TypedRegistry<Factory<T>>,
+}
+
+impl<T> FactoryRegistry<T> {
+    pub fn new() -> Self {
+        Self {
+            factories: TypedRegistry::new(),
+        }
+    }
+
+    /// Register factory for provided type, replacing previous one.
+    pub fn register_factory<Type, F>(&mut self, factory: F)
+    where
+        Type: 'static,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.factories.register::<Type>(Box::new(factory));
+    }
+
+    /// Build new value using factory registered for provided type.
+    pub fn create<Type>(&self) -> Result<T, NotRegistered>
+    where
+        Type: 'static,
+    {
+        self.factories.get::<Type>().map(|factory| factory())
+    }
+
+    pub fn is_registered<Type>(&self) -> bool
+    where
+        Type: 'static,
+    {
+        self.factories.is_registered::<Type>()
+    }
+
+    pub fn len(&self) -> usize {
+        self.factories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+}
+
+impl<T> Default for FactoryRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn factory_runs_on_every_create() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let mut factories = FactoryRegistry::new();
+        factories.register_factory::<u8, _>(|| CALLS.fetch_add(1, Ordering::SeqCst));
+
+        assert_eq!(factories.create::<u8>(), Ok(0));
+        assert_eq!(factories.create::<u8>(), Ok(1));
+        assert_eq!(factories.create::<u16>().unwrap_err().type_name(), "u16");
+    }
+}
@@ -0,0 +1,151 @@
+//! C API for looking up published values by stable type hash.
+#![allow(unsafe_code)]
+
+use std::collections::HashMap;
+use std::os::raw::c_void;
+use std::sync::RwLock;
+
+use crate::stable_type_hash;
+
+/// Table of `'static` values, addressed by [`stable_type_hash`], for sharing
+/// with C and C++ code.
+///
+/// Rust side publishes values, typically obtained from
+/// [`StaticTypeMap`](crate::StaticTypeMap), and passes pointer to the table
+/// to host application, which looks values up with
+/// [`generic_static_lookup`]. Values are exposed as untyped pointers, so
+/// both sides must agree on their layout.
+///
+/// ```c
+/// const void *generic_static_lookup(const FfiTypeMap *map, uint64_t hash);
+/// bool generic_static_insert(const FfiTypeMap *map, uint64_t hash, const void *value);
+/// size_t generic_static_len(const FfiTypeMap *map);
+/// ```
+///
+/// ```
+/// use generic_static::{stable_type_hash, FfiTypeMap};
+///
+/// static TABLE: FfiTypeMap = FfiTypeMap::new();
+/// static ANSWER: u32 = 42;
+///
+/// assert!(TABLE.publish::<u8, _>(&ANSWER));
+/// let ptr = unsafe { generic_static::generic_static_lookup(&TABLE, stable_type_hash::<u8>()) };
+/// assert_eq!(ptr, &ANSWER as *const u32 as *const _);
+/// ```
+pub struct FfiTypeMap {
+    values: RwLock<Option<HashMap<u64, usize>>>,
+}
+
+impl FfiTypeMap {
+    pub const fn new() -> Self {
+        Self {
+            values: RwLock::new(None),
+        }
+    }
+
+    /// Publish value under stable hash of `Type`, unless some value is
+    /// already published. Returns whether value was published.
+    pub fn publish<Type, V>(&self, value: &'static V) -> bool
+    where
+        Type: ?Sized + 'static,
+    {
+        self.insert(stable_type_hash::<Type>(), value as *const V as usize)
+    }
+
+    /// Address of value published under provided hash.
+    pub fn lookup(&self, hash: u64) -> Option<*const c_void> {
+        let values = self.values.read().unwrap();
+        let address = *values.as_ref()?.get(&hash)?;
+        Some(address as *const c_void)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.read().unwrap().as_ref().map_or(0, HashMap::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&self, hash: u64, address: usize) -> bool {
+        let mut values = self.values.write().unwrap();
+        let values = values.get_or_insert_with(HashMap::new);
+        if values.contains_key(&hash) {
+            return false;
+        }
+        values.insert(hash, address);
+        true
+    }
+}
+
+impl Default for FfiTypeMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Look up value published under provided hash. Returns null if `map` is
+/// null or nothing is published.
+///
+/// # Safety
+///
+/// `map` must be null or point to live `FfiTypeMap`.
+#[no_mangle]
+pub unsafe extern "C" fn generic_static_lookup(map: *const FfiTypeMap, hash: u64) -> *const c_void {
+    match map.as_ref() {
+        Some(map) => map.lookup(hash).unwrap_or(std::ptr::null()),
+        None => std::ptr::null(),
+    }
+}
+
+/// Publish value from host application under provided hash. Returns
+/// whether value was published.
+///
+/// # Safety
+///
+/// `map` must be null or point to live `FfiTypeMap`, and `value` must stay
+/// valid for the rest of the program.
+#[no_mangle]
+pub unsafe extern "C" fn generic_static_insert(
+    map: *const FfiTypeMap,
+    hash: u64,
+    value: *const c_void,
+) -> bool {
+    match map.as_ref() {
+        Some(map) if !value.is_null() => map.insert(hash, value as usize),
+        _ => false,
+    }
+}
+
+/// Number of published values, or 0 if `map` is null.
+///
+/// # Safety
+///
+/// `map` must be null or point to live `FfiTypeMap`.
+#[no_mangle]
+pub unsafe extern "C" fn generic_static_len(map: *const FfiTypeMap) -> usize {
+    map.as_ref().map_or(0, FfiTypeMap::len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn c_api_sees_published_values() {
+        static TABLE: FfiTypeMap = FfiTypeMap::new();
+        static VALUE: u64 = 7;
+        static OTHER: u64 = 8;
+        let hash = stable_type_hash::<String>();
+
+        let value = &VALUE as *const u64 as *const c_void;
+        unsafe {
+            assert!(generic_static_lookup(&TABLE, hash).is_null());
+            assert!(generic_static_insert(&TABLE, hash, value));
+            assert!(!TABLE.publish::<String, _>(&OTHER));
+            assert_eq!(generic_static_len(&TABLE), 1);
+            assert_eq!(generic_static_lookup(&TABLE, hash), value);
+            assert!(generic_static_lookup(std::ptr::null(), hash).is_null());
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use once_cell::sync::{Lazy, OnceCell};
+
+use crate::StaticTypeMap;
+
+/// Key of global slot of type `T` tagged with `Tag`.
+struct GlobalKey<T, Tag: ?Sized>(PhantomData<(T, Box<Tag>)>);
+
+static GLOBALS: Lazy<StaticTypeMap<Box<dyn Any + Send + Sync>>> =
+    Lazy::new(|| StaticTypeMap::named("generic_static::global"));
+
+/// Process-wide slot of type `T`, one per pair of `T` and `Tag`.
+///
+/// Shortcut for the most common use of the crate, which does not require
+/// declaring any map: slot is created on first access, and initialized with
+/// `get_or_init`. `Tag` distinguishes unrelated globals of the same type,
+/// use `()` if there is no need for that.
+///
+/// ```
+/// use generic_static::global;
+///
+/// struct Retries;
+///
+/// fn registry<T: 'static>() -> &'static Vec<&'static str> {
+///     global::<Vec<&'static str>, T>().get_or_init(|| vec![std::any::type_name::<T>()])
+/// }
+///
+/// assert_eq!(registry::<u8>(), &["u8"]);
+/// assert_eq!(*global::<u32, Retries>().get_or_init(|| 3), 3);
+/// assert_eq!(global::<u32, ()>().get(), None);
+/// ```
+pub fn global<T, Tag>() -> &'static OnceCell<T>
+where
+    T: Send + Sync + 'static,
+    Tag: ?Sized + 'static,
+{
+    let slot = GLOBALS.call_once::<GlobalKey<T, Tag>, _>(|| Box::new(OnceCell::<T>::new()));
+    // Value for the key is always initialized with `OnceCell<T>`
+    slot.downcast_ref::<OnceCell<T>>().unwrap()
+}
+
+/// Tag of slots used by [`singleton`].
+struct Singleton;
+
+/// Per-type singleton, created with `Default` on first access.
+///
+/// ```
+/// use generic_static::singleton;
+///
+/// #[derive(Default)]
+/// struct Config {
+///     verbose: bool,
+/// }
+///
+/// assert!(!singleton::<Config>().verbose);
+/// assert!(std::ptr::eq(singleton::<Config>(), singleton::<Config>()));
+/// ```
+pub fn singleton<T>() -> &'static T
+where
+    T: Default + Send + Sync + 'static,
+{
+    global::<T, Singleton>().get_or_init(T::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_separate_globals_of_same_type() {
+        struct A;
+        struct B;
+        global::<String, A>().get_or_init(|| "a".to_string());
+
+        assert_eq!(global::<String, A>().get().unwrap(), "a");
+        assert_eq!(global::<String, B>().get(), None);
+        assert!(std::ptr::eq(global::<String, A>(), global::<String, A>()));
+    }
+}
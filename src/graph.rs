@@ -0,0 +1,73 @@
+use std::fmt::Write;
+
+use crate::StaticTypeMap;
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Render observed initialization dependencies in Graphviz DOT format.
+    ///
+    /// Edge `A -> B` is recorded when initializer of `A` requests value of
+    /// entry `B`, which is not initialized yet. `A` may belong to different
+    /// map. Dependencies on already initialized values are not observed.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.call_once::<u16, _>(|| MAP.call_once::<u8, _>(|| 8) + 8);
+    /// assert!(MAP.dependency_graph_dot().contains("\"u16\" -> \"u8\";"));
+    /// ```
+    pub fn dependency_graph_dot(&self) -> String {
+        let mut names: Vec<_> = self.entries().iter().map(|e| e.type_name()).collect();
+        names.sort_unstable();
+
+        let mut out = String::from("digraph {\n");
+        for name in &names {
+            let _ = writeln!(out, "    {:?};", name);
+        }
+        for entry in self.entries() {
+            for dependent in entry.dependents.lock().unwrap().iter() {
+                let _ = writeln!(out, "    {:?} -> {:?};", dependent, entry.type_name());
+            }
+        }
+        out.push('}');
+        out.push('\n');
+        out
+    }
+}
+
+/// Render declared dependencies of registered initializers in Graphviz DOT
+/// format. Edge `A -> B` means that `A` runs after `B`.
+#[cfg(feature = "inventory")]
+pub fn initializers_dot() -> String {
+    let mut out = String::from("digraph {\n");
+    for initializer in crate::initializers() {
+        let _ = writeln!(out, "    {:?};", initializer.name());
+        for dependency in initializer.dependencies() {
+            let _ = writeln!(out, "    {:?} -> {:?};", initializer.name(), dependency);
+        }
+    }
+    out.push('}');
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn nested_initialization_is_recorded() {
+        static OUTER: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        static INNER: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        OUTER.call_once::<u8, _>(|| INNER.call_once::<u16, _>(|| 16).to_string());
+
+        assert_eq!(
+            INNER.dependency_graph_dot(),
+            "digraph {\n    \"u16\";\n    \"u8\" -> \"u16\";\n}\n"
+        );
+        assert_eq!(OUTER.dependency_graph_dot(), "digraph {\n    \"u8\";\n}\n");
+    }
+}
@@ -0,0 +1,39 @@
+/// Hash of type, stable across runs and builds for the same type name.
+///
+/// Unlike `TypeId`, which may change between compilations, hash is computed
+/// from `std::any::type_name` with 64 bit FNV-1a, so it can be precomputed
+/// and shared with code outside of the Rust program, see [`FfiTypeMap`].
+/// Type names themselves are not guaranteed to be stable between compiler
+/// versions.
+///
+/// [`FfiTypeMap`]: crate::FfiTypeMap
+///
+/// ```
+/// use generic_static::stable_type_hash;
+///
+/// assert_eq!(stable_type_hash::<u8>(), stable_type_hash::<u8>());
+/// assert_ne!(stable_type_hash::<u8>(), stable_type_hash::<u16>());
+/// ```
+pub fn stable_type_hash<Type: ?Sized>() -> u64 {
+    fnv1a(std::any::type_name::<Type>())
+}
+
+/// 64 bit FNV-1a hash, stable across runs and compiler versions, unlike
+/// `DefaultHasher`.
+pub(crate) fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_fnv1a() {
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(stable_type_hash::<u8>(), fnv1a("u8"));
+    }
+}
@@ -0,0 +1,182 @@
+use std::any::TypeId;
+
+use crate::{Entry, StaticTypeMap};
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Create map, which falls back to `parent` for types without value.
+    ///
+    /// Lookups on child return value stored in the child if any, otherwise
+    /// delegate to the parent, so values are shared with the parent and its
+    /// other children. This applies to every entry point, including
+    /// [`raw::entry`](crate::raw::entry). Values specific to the child
+    /// are stored with [`insert`](Self::insert).
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static DEFAULTS: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    /// static TENANT: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::child_of(&DEFAULTS));
+    ///
+    /// TENANT.insert::<u8>(1);
+    /// assert_eq!(*TENANT.call_once::<u8, _>(|| 10), 1);
+    /// assert_eq!(*TENANT.call_once::<u16, _>(|| 20), 20);
+    /// assert_eq!(*DEFAULTS.call_once::<u8, _>(|| 10), 10);
+    /// assert_eq!(*DEFAULTS.call_once::<u16, _>(|| unreachable!()), 20);
+    /// ```
+    pub fn child_of(parent: &'static StaticTypeMap<T, NS>) -> Self {
+        Self {
+            parent: Some(parent),
+            ..Self::new()
+        }
+    }
+
+    /// Map, this map falls back to, see [`child_of`](Self::child_of).
+    pub fn parent(&self) -> Option<&'static StaticTypeMap<T, NS>> {
+        self.parent
+    }
+
+    /// Store value corresponding to provided type in this map.
+    ///
+    /// If value was already initialized, `value` is dropped and existing
+    /// value is returned.
+    #[track_caller]
+    pub fn insert<Type>(&'static self, value: T) -> &'static T
+    where
+        Type: 'static,
+    {
+        self.get_or_init(self.entry::<Type>(), move || value)
+    }
+
+    /// Map, which holds value of type with provided `TypeId`: this map,
+    /// unless it has no value of its own and falls back to the parent.
+    ///
+    /// All entry points initializing, returning or inspecting values look
+    /// entry up in the owner, so children behave the same regardless of
+    /// entry point.
+    pub(crate) fn owner(&self, id: TypeId) -> &Self {
+        match self.parent {
+            Some(parent) if !self.has_own_value(id) => parent.owner(id),
+            _ => self,
+        }
+    }
+
+    /// Entry of type with provided `TypeId` in its owner, if it exists.
+    pub(crate) fn owned_entry(&self, id: TypeId) -> Option<&'static Entry<T>> {
+        self.owner(id).get_entry(id)
+    }
+
+    fn has_own_value(&self, id: TypeId) -> bool {
+        let entry = match self.get_entry(id) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        #[cfg(any(test, feature = "testing"))]
+        if entry.current_override().is_some() {
+            return true;
+        }
+        entry.cell.get().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn grandchild_inherits_through_chain() {
+        static ROOT: Lazy<StaticTypeMap<&str>> = Lazy::new(StaticTypeMap::new);
+        static CHILD: Lazy<StaticTypeMap<&str>> = Lazy::new(|| StaticTypeMap::child_of(&ROOT));
+        static GRANDCHILD: Lazy<StaticTypeMap<&str>> =
+            Lazy::new(|| StaticTypeMap::child_of(&CHILD));
+
+        ROOT.insert::<u8>("root");
+        CHILD.insert::<u16>("child");
+
+        assert_eq!(*GRANDCHILD.call_once::<u8, _>(|| "new"), "root");
+        assert_eq!(*GRANDCHILD.call_once::<u16, _>(|| "new"), "child");
+        assert!(std::ptr::eq(GRANDCHILD.parent().unwrap(), &*CHILD));
+    }
+
+    #[test]
+    fn every_entry_point_delegates_to_parent() {
+        static ROOT: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        static CHILD: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::child_of(&ROOT));
+
+        assert_eq!(CHILD.try_call_once::<u8, _>(|| 8), Ok(&8));
+        assert_eq!(*CHILD.call_once_local::<u16, _>(|| 16), 16);
+        assert_eq!(crate::raw::entry::<u32, _, _>(&CHILD).publish(32), Ok(&32));
+
+        assert_eq!(*ROOT.call_once::<u8, _>(|| unreachable!()), 8);
+        assert_eq!(*ROOT.call_once::<u16, _>(|| unreachable!()), 16);
+        assert_eq!(*ROOT.call_once::<u32, _>(|| unreachable!()), 32);
+        assert_eq!(CHILD.call_once_or_global::<u8>(), Some(&8));
+        assert_eq!(CHILD.leaked_entries(), 0);
+    }
+
+    /// Child of map holding value of `u8`, which child does not have.
+    fn child_of_initialized() -> &'static StaticTypeMap<u32> {
+        static ROOT: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        static CHILD: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::child_of(&ROOT));
+        ROOT.call_once::<u8, _>(|| 8);
+        &CHILD
+    }
+
+    #[test]
+    fn cell_of_reads_parent_value() {
+        let child = child_of_initialized();
+        assert_eq!(child.cell_of::<u8>().get(), Some(&8));
+        assert_eq!(child.leaked_entries(), 0);
+    }
+
+    #[test]
+    fn raw_cells_read_parent_value() {
+        let child = child_of_initialized();
+        assert_eq!(crate::raw::cell::<u8, _, _>(child).get(), Some(&8));
+        let cell = crate::raw::get_cell(child, TypeId::of::<u8>()).unwrap();
+        assert_eq!(cell.get(), Some(&8));
+    }
+
+    #[test]
+    fn try_initialized_reads_parent_value() {
+        assert_eq!(child_of_initialized().try_initialized::<u8>(), Ok(&8));
+    }
+
+    #[test]
+    fn init_location_reads_parent_value() {
+        let child = child_of_initialized();
+        let location = child.parent().unwrap().init_location::<u8>();
+        assert!(location.is_some());
+        assert_eq!(child.init_location::<u8>(), location);
+    }
+
+    #[test]
+    fn dyn_lookup_reads_parent_value() {
+        let dynamic = crate::DynStaticMap::new(child_of_initialized());
+        assert_eq!(crate::DynTypeMap::lookup(&dynamic, TypeId::of::<u8>()), Some(&8));
+    }
+
+    #[test]
+    #[cfg(feature = "access-time")]
+    fn last_access_reads_parent_value() {
+        assert!(child_of_initialized().last_access::<u8>().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "init-trace")]
+    fn init_trace_reads_parent_value() {
+        assert!(child_of_initialized().init_trace::<u8>().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "type-map")]
+    fn type_map_interop_reads_parent_value() {
+        let child = child_of_initialized();
+        let mut exported = type_map::TypeMap::new();
+        assert!(child.export_to_type_map::<u8>(&mut exported));
+        assert_eq!(exported.get::<u32>(), Some(&8));
+        let empty = type_map::TypeMap::new();
+        assert_eq!(child.promote_from_type_map::<u8>(&empty), Some(&8));
+    }
+}
@@ -0,0 +1,64 @@
+use std::any::TypeId;
+use std::sync::Arc;
+
+use crate::StaticTypeMap;
+
+pub(crate) type InitHook<T> = Arc<dyn Fn(TypeId, &T) + Send + Sync>;
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Register callback, called after each successful initialization
+    /// with `TypeId` of key type and initialized value.
+    ///
+    /// Callback is called on the thread which performed initialization,
+    /// after any locks held by the map were released, so it may initialize
+    /// other types or register more callbacks.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::any::TypeId;
+    /// use std::sync::Mutex;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    /// static SEEN: Mutex<Vec<TypeId>> = Mutex::new(Vec::new());
+    ///
+    /// MAP.on_init(|id, _| SEEN.lock().unwrap().push(id));
+    /// MAP.call_once::<u8, _>(|| 1);
+    /// MAP.call_once::<u8, _>(|| 2);
+    ///
+    /// assert_eq!(*SEEN.lock().unwrap(), vec![TypeId::of::<u8>()]);
+    /// ```
+    pub fn on_init<Hook>(&self, hook: Hook)
+    where
+        Hook: Fn(TypeId, &T) + Send + Sync + 'static,
+    {
+        self.hooks.write().unwrap().push(Arc::new(hook));
+    }
+
+    pub(crate) fn run_init_hooks(&self, id: TypeId, value: &T) {
+        let hooks = self.hooks.read().unwrap().clone();
+        for hook in hooks {
+            hook(id, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn hooks_may_use_the_map() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.on_init(|id, _| {
+            if id == TypeId::of::<u8>() {
+                MAP.on_init(|_, _| {});
+                MAP.call_once::<u16, _>(|| 16);
+            }
+        });
+
+        MAP.call_once::<u8, _>(|| 8);
+        assert_eq!(MAP.try_initialized::<u16>(), Ok(&16));
+    }
+}
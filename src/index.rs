@@ -0,0 +1,97 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Allocator of dense per type indices.
+///
+/// Each type gets stable index on first use. Indices are assigned
+/// sequentially starting from zero, so they can be used to address
+/// vectors or bitsets.
+///
+/// ```
+/// use generic_static::StaticTypeIndex;
+///
+/// let index = StaticTypeIndex::new();
+/// assert_eq!(index.index_of::<u32>(), 0);
+/// assert_eq!(index.index_of::<u64>(), 1);
+/// assert_eq!(index.index_of::<u32>(), 0);
+/// assert_eq!(index.get::<u8>(), None);
+/// ```
+pub struct StaticTypeIndex {
+    map: RwLock<HashMap<TypeId, usize>>,
+}
+
+impl StaticTypeIndex {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Index corresponding to provided type, assigning new one if type
+    /// was not seen before.
+    pub fn index_of<Type>(&self) -> usize
+    where
+        Type: 'static,
+    {
+        if let Some(index) = self.get::<Type>() {
+            return index;
+        }
+        let mut writer = self.map.write().unwrap();
+        let next = writer.len();
+        *writer.entry(TypeId::of::<Type>()).or_insert(next)
+    }
+
+    /// Index corresponding to provided type, if one was already assigned.
+    pub fn get<Type>(&self) -> Option<usize>
+    where
+        Type: 'static,
+    {
+        self.map.read().unwrap().get(&TypeId::of::<Type>()).cloned()
+    }
+
+    /// Number of assigned indices.
+    pub fn len(&self) -> usize {
+        self.map.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for StaticTypeIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn concurrent_indices_are_dense() {
+        let index = Arc::new(StaticTypeIndex::new());
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let index = index.clone();
+                thread::spawn(move || {
+                    vec![
+                        index.index_of::<u8>(),
+                        index.index_of::<u16>(),
+                        index.index_of::<u32>(),
+                    ]
+                })
+            })
+            .collect();
+        let results: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+
+        assert!(results.iter().all(|r| *r == results[0]));
+        let mut indices = results[0].clone();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+}
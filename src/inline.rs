@@ -0,0 +1,185 @@
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::sync::{Condvar, Mutex, RwLock};
+use std::thread::{self, ThreadId};
+
+enum Slot<T> {
+    Initializing(ThreadId),
+    Ready(T),
+}
+
+/// Map of small `Copy` values, stored directly in the table.
+///
+/// Unlike [`StaticTypeMap`](crate::StaticTypeMap), no allocation is made
+/// per type and values are returned by copy, so lookup does not follow a
+/// pointer. Initializer still runs once per type, without holding lock of
+/// the map.
+///
+/// ```
+/// use generic_static::InlineTypeMap;
+///
+/// let sizes = InlineTypeMap::new();
+/// assert_eq!(sizes.call_once::<u64, _>(|| std::mem::size_of::<u64>()), 8);
+/// assert_eq!(sizes.get::<u64>(), Some(8));
+/// assert_eq!(sizes.get::<u32>(), None);
+/// ```
+pub struct InlineTypeMap<T: Copy> {
+    map: RwLock<HashMap<TypeId, Slot<T>>>,
+    /// Notified when initialization of any type finishes.
+    ready: Condvar,
+    wait: Mutex<()>,
+}
+
+impl<T: Copy> InlineTypeMap<T> {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+            ready: Condvar::new(),
+            wait: Mutex::new(()),
+        }
+    }
+
+    /// Get value corresponding to provided type, initializing it if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics on reentrant initialization of the same type. If initializer
+    /// panics, value stays uninitialized and next call runs initializer
+    /// again.
+    pub fn call_once<Type, Init>(&self, f: Init) -> T
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        let id = TypeId::of::<Type>();
+        match self.get_by_id(id) {
+            Some(value) => value,
+            None => self.init(id, type_name::<Type>(), f),
+        }
+    }
+
+    /// Get value corresponding to provided type, if it is initialized.
+    pub fn get<Type>(&self) -> Option<T>
+    where
+        Type: 'static,
+    {
+        self.get_by_id(TypeId::of::<Type>())
+    }
+
+    fn get_by_id(&self, id: TypeId) -> Option<T> {
+        match self.map.read().unwrap().get(&id) {
+            Some(Slot::Ready(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Mark type as being initialized by current thread. Returns `false` if
+    /// type is initialized or initializing on another thread.
+    fn claim(&self, id: TypeId, type_name: &'static str) -> bool {
+        let current = thread::current().id();
+        let mut writer = self.map.write().unwrap();
+        match writer.get(&id) {
+            Some(Slot::Initializing(thread)) if *thread == current => {
+                panic!(
+                    "reentrant initialization of `{}` in InlineTypeMap",
+                    type_name
+                )
+            }
+            Some(_) => false,
+            None => {
+                writer.insert(id, Slot::Initializing(current));
+                true
+            }
+        }
+    }
+
+    #[inline(never)]
+    fn init<Init>(&self, id: TypeId, type_name: &'static str, f: Init) -> T
+    where
+        Init: FnOnce() -> T,
+    {
+        if !self.claim(id, type_name) {
+            return self.wait_for(id, type_name, f);
+        }
+        let guard = ClaimGuard { map: self, id };
+        let value = f();
+        std::mem::forget(guard);
+        self.map.write().unwrap().insert(id, Slot::Ready(value));
+        self.notify();
+        value
+    }
+
+    /// Wait until other thread finishes initialization. If it fails, try
+    /// initializing again.
+    fn wait_for<Init>(&self, id: TypeId, type_name: &'static str, f: Init) -> T
+    where
+        Init: FnOnce() -> T,
+    {
+        let mut lock = self.wait.lock().unwrap();
+        loop {
+            match self.map.read().unwrap().get(&id) {
+                Some(Slot::Ready(value)) => return *value,
+                Some(Slot::Initializing(_)) => {}
+                None => break,
+            }
+            lock = self.ready.wait(lock).unwrap();
+        }
+        drop(lock);
+        self.init(id, type_name, f)
+    }
+
+    fn notify(&self) {
+        let _lock = self.wait.lock().unwrap();
+        self.ready.notify_all();
+    }
+}
+
+impl<T: Copy> Default for InlineTypeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Releases claim of type, if initializer panicked.
+struct ClaimGuard<'a, T: Copy> {
+    map: &'a InlineTypeMap<T>,
+    id: TypeId,
+}
+
+impl<T: Copy> Drop for ClaimGuard<'_, T> {
+    fn drop(&mut self) {
+        if let Ok(mut writer) = self.map.map.write() {
+            writer.remove(&self.id);
+        }
+        self.map.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn concurrent_callers_run_initializer_once() {
+        static MAP: once_cell::sync::Lazy<InlineTypeMap<usize>> =
+            once_cell::sync::Lazy::new(InlineTypeMap::new);
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                thread::spawn(|| {
+                    MAP.call_once::<u8, _>(|| {
+                        thread::sleep(Duration::from_millis(20));
+                        CALLS.fetch_add(1, Ordering::SeqCst) + 10
+                    })
+                })
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), 10);
+        }
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+}
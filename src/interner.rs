@@ -0,0 +1,68 @@
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+/// Per type string interner.
+///
+/// Each key type has separate set of interned strings. Interning same
+/// string for same type twice returns the same `&'static str`.
+///
+/// Interned strings stay on heap until program terminated.
+///
+/// ```
+/// use generic_static::StaticTypeInterner;
+///
+/// let interner = StaticTypeInterner::new();
+/// let a = interner.intern::<u32>("label");
+/// let b = interner.intern::<u32>(&String::from("label"));
+///
+/// assert!(std::ptr::eq(a, b));
+/// assert!(!std::ptr::eq(a, interner.intern::<u64>("label")));
+/// ```
+pub struct StaticTypeInterner {
+    map: RwLock<HashMap<TypeId, HashSet<&'static str>>>,
+}
+
+impl StaticTypeInterner {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Intern string for provided type.
+    pub fn intern<Type>(&self, s: &str) -> &'static str
+    where
+        Type: 'static,
+    {
+        if let Some(interned) = self.get::<Type>(s) {
+            return interned;
+        }
+        let mut writer = self.map.write().unwrap();
+        let set = writer.entry(TypeId::of::<Type>()).or_default();
+        if let Some(interned) = set.get(s) {
+            return interned;
+        }
+        let interned: &'static str = crate::memory::intentional_leak_str(s);
+        set.insert(interned);
+        interned
+    }
+
+    /// Get string interned for provided type, without interning it.
+    pub fn get<Type>(&self, s: &str) -> Option<&'static str>
+    where
+        Type: 'static,
+    {
+        self.map
+            .read()
+            .unwrap()
+            .get(&TypeId::of::<Type>())
+            .and_then(|set| set.get(s).cloned())
+    }
+}
+
+impl Default for StaticTypeInterner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
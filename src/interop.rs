@@ -0,0 +1,62 @@
+use std::any::TypeId;
+
+use type_map::TypeMap;
+
+use crate::StaticTypeMap;
+
+impl<T, NS: 'static> StaticTypeMap<T, NS>
+where
+    T: Clone + 'static,
+{
+    /// Initialize value corresponding to provided type with copy of `T`
+    /// stored in `source`.
+    ///
+    /// If value was already initialized, it is returned unchanged. Returns
+    /// `None` if value is not initialized and `source` does not contain `T`.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use type_map::TypeMap;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let mut extensions = TypeMap::new();
+    /// extensions.insert("config".to_string());
+    ///
+    /// assert_eq!(MAP.promote_from_type_map::<u32>(&extensions).unwrap(), "config");
+    /// assert_eq!(MAP.call_once::<u32, _>(|| unreachable!()), "config");
+    /// ```
+    pub fn promote_from_type_map<Type>(&'static self, source: &TypeMap) -> Option<&'static T>
+    where
+        Type: 'static,
+    {
+        let existing = self
+            .owned_entry(TypeId::of::<Type>())
+            .and_then(|entry| entry.cell.get());
+        if let Some(value) = existing {
+            return Some(value);
+        }
+        let value = source.get::<T>()?.clone();
+        Some(self.call_once::<Type, _>(|| value))
+    }
+
+    /// Insert copy of value corresponding to provided type into `dest`.
+    ///
+    /// Returns `true` if value was initialized and copied.
+    pub fn export_to_type_map<Type>(&self, dest: &mut TypeMap) -> bool
+    where
+        Type: 'static,
+    {
+        let value = self
+            .owned_entry(TypeId::of::<Type>())
+            .and_then(|entry| entry.cell.get());
+        match value {
+            Some(value) => {
+                dest.insert(value.clone());
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::any::TypeId;
+
+use crate::StaticTypeMap;
+
+/// Map of static values, initialized by single map-level initializer.
+///
+/// Unlike [`StaticTypeMap`], initializer is provided once at construction,
+/// so all lookups are guaranteed to use the same initialization logic.
+///
+/// ```
+/// use generic_static::LazyTypeMap;
+/// use once_cell::sync::Lazy;
+/// use std::any::TypeId;
+///
+/// static IDS: Lazy<LazyTypeMap<String>> = Lazy::new(|| {
+///     LazyTypeMap::new(|id| format!("{:?}", id))
+/// });
+///
+/// assert_eq!(IDS.get::<u32>(), &format!("{:?}", TypeId::of::<u32>()));
+/// ```
+pub struct LazyTypeMap<T: 'static, F = fn(TypeId) -> T> {
+    map: StaticTypeMap<T>,
+    init: F,
+}
+
+impl<T: 'static, F> LazyTypeMap<T, F>
+where
+    F: Fn(TypeId) -> T,
+{
+    pub fn new(init: F) -> Self {
+        Self {
+            map: StaticTypeMap::new(),
+            init,
+        }
+    }
+
+    /// Get static value corresponding to provided type.
+    ///
+    /// Value is initialized on first access by calling map initializer
+    /// with `TypeId` of `Type`.
+    pub fn get<Type>(&'static self) -> &'static T
+    where
+        Type: 'static,
+    {
+        self.map
+            .call_once::<Type, _>(|| (self.init)(TypeId::of::<Type>()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::OnceCell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn initializer_runs_once_per_type() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static MAP: OnceCell<LazyTypeMap<TypeId>> = OnceCell::new();
+        let map = MAP.get_or_init(|| {
+            LazyTypeMap::new(|id| {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                id
+            })
+        });
+
+        assert_eq!(*map.get::<u8>(), TypeId::of::<u8>());
+        assert_eq!(*map.get::<u16>(), TypeId::of::<u16>());
+        assert_eq!(*map.get::<u8>(), TypeId::of::<u8>());
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    }
+}
@@ -95,55 +95,786 @@
 //! Current implementation uses RwLock to make it safe in concurrent
 //! applications, which will be slightly slower then regular
 
+#![cfg_attr(not(feature = "ffi"), forbid(unsafe_code))]
+#![cfg_attr(feature = "ffi", deny(unsafe_code))]
+
+#[cfg(feature = "access-time")]
+mod access;
+mod arc;
+mod builder;
+mod cached;
+mod channels;
+mod closed;
+pub mod core;
+mod counter;
+mod debug;
+mod dense;
+mod diagnostics;
+mod dispatch;
+mod dump;
+mod dynamic;
+mod entry_ref;
+#[cfg(feature = "inventory")]
+mod eager;
+mod error;
+mod factory;
+#[cfg(feature = "ffi")]
+mod ffi;
+mod global;
+mod graph;
+mod hash;
+mod hierarchy;
+mod hooks;
+mod index;
+mod inline;
+#[cfg(feature = "type-map")]
+mod interop;
+mod interner;
+mod lazy;
+mod limits;
+mod local;
+mod macros;
+mod memory;
+#[cfg(feature = "metrics")]
+mod metric;
+mod multi;
+mod once;
+#[cfg(feature = "disk-cache")]
+mod persist;
+mod provide;
+pub mod raw;
+mod reentrancy;
+mod ref_map;
+mod refresh;
+mod registry;
+mod report;
+mod seal;
+#[cfg(feature = "serde")]
+mod serialize;
+mod send;
+mod set;
+mod snapshot;
+#[cfg(feature = "tokio")]
+mod spawn;
+mod stats;
+pub mod sync;
+#[cfg(feature = "erased-serde")]
+mod tagged;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod unsync;
+mod validate;
+mod variants;
+mod vec;
+#[cfg(feature = "instance-check")]
+mod version;
+mod warm;
+mod watchdog;
+
+pub use arc::ArcTypeMap;
+pub use builder::StaticTypeMapBuilder;
+pub use cached::cached_dyn;
+pub use channels::StaticTypeChannels;
+pub use closed::ClosedKey;
+pub use counter::StaticTypeCounter;
+pub use dense::DenseTypeMap;
+pub use diagnostics::NotInitialized;
+pub use dispatch::{BuildTable, DispatchTables};
+pub use dump::{dump_all, MapDump};
+pub use dynamic::{DynStaticMap, DynTypeMap};
+pub use entry_ref::EntryRef;
+#[cfg(feature = "inventory")]
+pub use eager::{init_all, initializers, Initializer};
+#[cfg(feature = "rayon")]
+pub use eager::par_init_all;
+pub use error::Error;
+pub use factory::FactoryRegistry;
+#[cfg(feature = "ffi")]
+pub use ffi::{generic_static_insert, generic_static_len, generic_static_lookup, FfiTypeMap};
+pub use global::{global, singleton};
+#[cfg(feature = "inventory")]
+pub use graph::initializers_dot;
+pub use hash::stable_type_hash;
+pub use index::StaticTypeIndex;
+pub use inline::InlineTypeMap;
+pub use interner::StaticTypeInterner;
+pub use lazy::LazyTypeMap;
+pub use memory::{LeakReport, LeakedEntry};
+#[cfg(feature = "heap-size")]
+pub use memory::HeapSize;
+pub use multi::StaticTypeMultiMap;
+pub use once::TypeOnce;
+#[cfg(feature = "disk-cache")]
+pub use persist::DiskCache;
+pub use provide::ProvideValue;
+pub use ref_map::StaticTypeRefMap;
+pub use refresh::Refresher;
+pub use registry::{NotRegistered, TypedRegistry};
+pub use send::SendStaticTypeMap;
+#[cfg(feature = "serde")]
+pub use serialize::SnapshotTypes;
+pub use set::StaticTypeSet;
+pub use stats::{EntryStats, InitRecord, Stats};
+#[cfg(feature = "erased-serde")]
+pub use tagged::TagRegistry;
+pub use validate::ValidationError;
+pub use variants::OnceState;
+pub use vec::{ItemsGuard, StaticTypeVec};
+#[cfg(feature = "instance-check")]
+pub use version::{check_unique_instance, InstanceClaim};
+
+use hooks::InitHook;
+use limits::{EntryLimit, LeakBudget};
 use std::any::TypeId;
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::marker::PhantomData;
+use std::panic::Location;
+#[cfg(any(feature = "stats", feature = "access-time"))]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
 use once_cell::sync::OnceCell;
+use watchdog::Watchdog;
 
-pub struct StaticTypeMap<T: 'static> {
-    map: RwLock<HashMap<TypeId, &'static OnceCell<T>>>,
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "inventory")]
+    pub use inventory;
+    pub use once_cell::sync::OnceCell;
+    pub use std::marker::PhantomData;
 }
 
-impl<T: 'static> StaticTypeMap<T> {
+/// Map of static values, one per key type.
+///
+/// Optional namespace parameter `NS` makes maps of the same value type
+/// distinct types, so maps of independent subsystems can not be mixed up
+/// when passed around or used to implement traits.
+///
+/// ```
+/// use generic_static::StaticTypeMap;
+/// use once_cell::sync::Lazy;
+///
+/// struct Parser;
+/// struct Renderer;
+///
+/// static PARSER: Lazy<StaticTypeMap<String, Parser>> = Lazy::new(StaticTypeMap::new);
+/// static RENDERER: Lazy<StaticTypeMap<String, Renderer>> = Lazy::new(StaticTypeMap::new);
+///
+/// PARSER.call_once::<u8, _>(|| "parse u8".to_string());
+/// assert_eq!(RENDERER.call_once::<u8, _>(|| "render u8".to_string()), "render u8");
+/// ```
+pub struct StaticTypeMap<T: 'static, NS: 'static = Global> {
+    name: Option<&'static str>,
+    parent: Option<&'static StaticTypeMap<T, NS>>,
+    map: RwLock<KeyMap<&'static Entry<T>>>,
+    hooks: RwLock<Vec<InitHook<T>>>,
+    #[cfg(feature = "stats")]
+    hits: AtomicU64,
+    #[cfg(feature = "stats")]
+    misses: AtomicU64,
+    watchdog: RwLock<Option<Watchdog>>,
+    sealed: AtomicBool,
+    entry_limit: RwLock<Option<EntryLimit>>,
+    leak_budget: RwLock<Option<LeakBudget>>,
+    /// Number of values initialized by the map, see `Entry::order`.
+    initialized: AtomicUsize,
+    /// Value used for types without entry, see `set_global_default`.
+    global_default: OnceCell<T>,
+    namespace: PhantomData<fn() -> NS>,
+}
+
+/// Default namespace of [`StaticTypeMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Global;
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
     pub fn new() -> Self {
         Self {
-            map: RwLock::new(HashMap::new()),
+            name: None,
+            parent: None,
+            map: RwLock::new(KeyMap::default()),
+            hooks: RwLock::new(Vec::new()),
+            #[cfg(feature = "stats")]
+            hits: AtomicU64::new(0),
+            #[cfg(feature = "stats")]
+            misses: AtomicU64::new(0),
+            watchdog: RwLock::new(None),
+            sealed: AtomicBool::new(false),
+            entry_limit: RwLock::new(None),
+            leak_budget: RwLock::new(None),
+            initialized: AtomicUsize::new(0),
+            global_default: OnceCell::new(),
+            namespace: PhantomData,
         }
     }
 
+    /// Create map with human readable name, used in panic messages,
+    /// `Debug` output, tracing spans and metrics labels.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    ///
+    /// let map = StaticTypeMap::<u32>::named("fft_cache");
+    /// assert_eq!(map.name(), Some("fft_cache"));
+    /// assert_eq!(format!("{:?}", map), r#"StaticTypeMap { name: Some("fft_cache"), entries: [] }"#);
+    /// ```
+    pub fn named(name: &'static str) -> Self {
+        Self {
+            name: Some(name),
+            ..Self::new()
+        }
+    }
+
+    /// Name given to the map with [`named`](Self::named).
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
     /// Initialize static value corresponding to provided type.
     ///
     /// Initialized value will stay on heap until program terminated.
     /// No drop method will be called.
+    ///
+    /// Initializer is called without holding any lock of the map, so
+    /// initialization of one type never blocks initialization or lookup of
+    /// another. Concurrent calls for the same type wait until value is
+    /// initialized.
+    ///
+    /// Entry is allocated for every type, even if value is zero-sized. To
+    /// run code once per type without storing a value, use [`TypeOnce`].
+    #[track_caller]
     pub fn call_once<Type, Init>(&'static self, f: Init) -> &'static T
     where
         Type: 'static,
         Init: FnOnce() -> T,
     {
-        // If already initialized, just return stored value
-        let cell = {
-            let reader = self.map.read().unwrap();
-            reader.get(&TypeId::of::<Type>()).cloned() // Clone reference
+        let map = self.owner(TypeId::of::<Type>());
+        map.get_or_init(map.entry::<Type>(), f)
+    }
+
+    /// Initialize static value corresponding to type with provided `TypeId`.
+    ///
+    /// Object safe counterpart of [`StaticTypeMap::call_once`], usable where
+    /// key type is not known statically.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::any::TypeId;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let init: &dyn Fn() -> u32 = &|| 8;
+    /// assert_eq!(MAP.get_or_init_dyn(TypeId::of::<u8>(), init), &8);
+    /// assert_eq!(MAP.call_once::<u8, _>(|| unreachable!()), &8);
+    /// ```
+    #[track_caller]
+    pub fn get_or_init_dyn(&'static self, id: TypeId, f: &dyn Fn() -> T) -> &'static T {
+        let map = self.owner(id);
+        map.get_or_init(map.entry_by_id(id, UNKNOWN_TYPE_NAME), f)
+    }
+
+    #[track_caller]
+    fn get_or_init<Init>(&'static self, entry: &'static Entry<T>, f: Init) -> &'static T
+    where
+        Init: FnOnce() -> T,
+    {
+        self.get_or_init_tracked(entry, f).0
+    }
+
+    /// Same as `get_or_init`, but also returns whether value was initialized
+    /// by this call.
+    #[track_caller]
+    fn get_or_init_tracked<Init>(
+        &'static self,
+        entry: &'static Entry<T>,
+        f: Init,
+    ) -> (&'static T, bool)
+    where
+        Init: FnOnce() -> T,
+    {
+        self.get_or_init_at(entry, Location::caller(), f)
+    }
+
+    /// Same as `get_or_init_tracked`, but with explicit call site, for
+    /// callers which can not be `#[track_caller]`.
+    fn get_or_init_at<Init>(
+        &'static self,
+        entry: &'static Entry<T>,
+        location: &'static Location<'static>,
+        f: Init,
+    ) -> (&'static T, bool)
+    where
+        Init: FnOnce() -> T,
+    {
+        if let Some(value) = self.lookup(entry) {
+            return (value, false);
+        }
+        let mut f = Some(f);
+        self.init_slow(entry, location, false, &mut || (f.take().unwrap())())
+    }
+
+    /// Same as `get_or_init`, but runs initializer even if entry is
+    /// poisoned. Entry stays poisoned until initializer succeeds.
+    #[track_caller]
+    fn get_or_init_forced<Init>(&'static self, entry: &'static Entry<T>, f: Init) -> &'static T
+    where
+        Init: FnOnce() -> T,
+    {
+        if let Some(value) = self.lookup(entry) {
+            return value;
+        }
+        let mut f = Some(f);
+        self.init_slow(entry, Location::caller(), true, &mut || {
+            (f.take().unwrap())()
+        })
+        .0
+    }
+
+    /// Fast path of `get_or_init`: value of the entry, if it is available
+    /// without initialization.
+    fn lookup(&self, entry: &'static Entry<T>) -> Option<&'static T> {
+        #[cfg(any(test, feature = "testing"))]
+        if let Some(value) = entry.current_override() {
+            return Some(value);
+        }
+        let value = entry.cell.get()?;
+        #[cfg(feature = "stats")]
+        {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            entry.reads.fetch_add(1, Ordering::Relaxed);
+        }
+        #[cfg(feature = "access-time")]
+        entry.record_access();
+        Some(value)
+    }
+
+    /// Slow path of `get_or_init_tracked`.
+    ///
+    /// Initializer is type erased, so this is instantiated once per value
+    /// type instead of once per call site. With `force`, poisoned entry is
+    /// initialized and poison is cleared once initializer succeeds.
+    #[inline(never)]
+    fn init_slow(
+        &'static self,
+        entry: &'static Entry<T>,
+        location: &'static Location<'static>,
+        force: bool,
+        f: &mut dyn FnMut() -> T,
+    ) -> (&'static T, bool) {
+        self.try_init_slow(entry, location, force, f)
+            .unwrap_or_else(|err| self.fail(err))
+    }
+
+    /// Same as `init_slow`, but returns error if value can not be
+    /// initialized. Checks are done while holding initialization of the
+    /// entry, so they can not race with other initializers.
+    fn try_init_slow(
+        &'static self,
+        entry: &'static Entry<T>,
+        location: &'static Location<'static>,
+        force: bool,
+        f: &mut dyn FnMut() -> T,
+    ) -> Result<(&'static T, bool), Error> {
+        let poisoned = || Error::Poisoned {
+            type_name: entry.type_name(),
         };
-        if let Some(cell) = cell {
-            return cell.get_or_init(f);
-        }
-        let cell = {
-            let mut writer = self.map.write().unwrap();
-            let cell = writer
-                .entry(TypeId::of::<Type>())
-                .or_insert_with(|| {
-                    let boxed = Box::new(OnceCell::new());
-                    Box::leak(boxed)
+        if !force && entry.poisoned.load(Ordering::Acquire) {
+            return Err(poisoned());
+        }
+        if let Some(type_names) = reentrancy::cycle(entry) {
+            return Err(Error::Cycle { type_names });
+        }
+        if let Some(dependent) = reentrancy::current() {
+            entry.add_dependent(dependent);
+        }
+        let mut initialized = false;
+        let value = entry.cell.get_or_try_init(|| {
+            // Previous initializer might have panicked while we were waiting
+            if !force && entry.poisoned.load(Ordering::Acquire) {
+                return Err(poisoned());
+            }
+            if self.is_sealed() {
+                return Err(Error::Sealed {
+                    type_name: entry.type_name(),
                 });
-            *cell
+            }
+            initialized = true;
+            let _guard = reentrancy::InitGuard::enter(entry, entry.type_name());
+            let _poison = PoisonOnPanic(&entry.poisoned);
+            let _watch = self.watch(entry.type_name());
+            let start = Instant::now();
+            let value = initialize(self.label(), entry.type_name(), f);
+            let _ = entry.init_started.set(start);
+            let _ = entry.init_location.set(location);
+            #[cfg(feature = "init-trace")]
+            let _ = entry
+                .init_trace
+                .set(std::backtrace::Backtrace::force_capture());
+            let _ = entry.init_duration.set(start.elapsed());
+            #[cfg(feature = "timestamps")]
+            let _ = entry.init_time.set(std::time::SystemTime::now());
+            let _ = entry
+                .order
+                .set(self.initialized.fetch_add(1, Ordering::Relaxed));
+            entry.poisoned.store(false, Ordering::Release);
+            Ok(value)
+        })?;
+        #[cfg(feature = "access-time")]
+        entry.record_access();
+        #[cfg(feature = "stats")]
+        {
+            entry.reads.fetch_add(1, Ordering::Relaxed);
+            if initialized {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+            } else {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        if initialized {
+            self.run_init_hooks(entry.type_id, value);
+        }
+        Ok((value, initialized))
+    }
+
+    /// Get entry corresponding to provided type, inserting it if needed.
+    fn entry<Type>(&'static self) -> &'static Entry<T>
+    where
+        Type: 'static,
+    {
+        self.entry_by_id(TypeId::of::<Type>(), std::any::type_name::<Type>())
+    }
+
+    /// Get entry corresponding to type with provided `TypeId`, inserting it
+    /// if needed.
+    fn entry_by_id(&'static self, id: TypeId, type_name: &'static str) -> &'static Entry<T> {
+        self.try_entry_by_id(id, type_name)
+            .unwrap_or_else(|err| self.fail(err))
+    }
+
+    /// Same as `entry_by_id`, but returns error if entry can not be created.
+    /// Entry is only allocated once it is known to be inserted.
+    fn try_entry_by_id(
+        &'static self,
+        id: TypeId,
+        type_name: &'static str,
+    ) -> Result<&'static Entry<T>, Error> {
+        // If entry already exists, just return it
+        if let Some(entry) = self.get_entry(id) {
+            return Ok(self.existing_entry(entry, type_name));
+        }
+        let key = Key::new(id);
+        let mut writer = self.map.write().unwrap();
+        if let Some(entry) = writer.get(&key).copied() {
+            drop(writer);
+            return Ok(self.existing_entry(entry, type_name));
+        }
+        if self.is_sealed() {
+            return Err(Error::Sealed { type_name });
+        }
+        if let Some((max, hook)) = self.entry_limit() {
+            if writer.len() >= max {
+                let entries = writer.len() + 1;
+                drop(writer);
+                hook(type_name, entries);
+                return Err(Error::CapacityExceeded { type_name, max });
+            }
+        }
+        #[cfg(feature = "metrics")]
+        metric::entry_created(self.label(), type_name);
+        let entry: &'static Entry<T> = memory::intentional_leak_new(Entry::new(id, type_name));
+        writer.insert(key, entry);
+        let entries = writer.len();
+        drop(writer);
+        self.check_limits(entries);
+        Ok(entry)
+    }
+
+    /// Entry found by `try_entry_by_id`, accessed as type with provided name.
+    fn existing_entry(&self, entry: &'static Entry<T>, type_name: &'static str) -> &'static Entry<T> {
+        #[cfg(debug_assertions)]
+        entry.check_type_name(type_name, self.label());
+        entry.learn_type_name(type_name);
+        entry
+    }
+
+    /// Panic with description of error, for panicking counterparts of
+    /// fallible operations.
+    fn fail(&self, err: Error) -> ! {
+        match (err, self.name) {
+            (Error::Poisoned { type_name }, Some(name)) => panic!(
+                "initializer of `{}` in map `{}` panicked, value is poisoned",
+                type_name, name
+            ),
+            (Error::Cycle { type_names }, Some(name)) => panic!(
+                "reentrant initialization of `{}` in map `{}`",
+                type_names[0], name
+            ),
+            (Error::Cycle { type_names }, None) => panic!(
+                "reentrant initialization of `{}` in `StaticTypeMap<{}>` at {:p}",
+                type_names[0],
+                std::any::type_name::<T>(),
+                self
+            ),
+            (Error::Sealed { type_name }, _) => panic!(
+                "initialization of `{}` in sealed map `{}`",
+                type_name,
+                self.label()
+            ),
+            (Error::CapacityExceeded { type_name, max }, _) => panic!(
+                "can not create entry of `{}`, map `{}` is limited to {} entries",
+                type_name,
+                self.label(),
+                max
+            ),
+            (err, _) => panic!("{}", err),
+        }
+    }
+
+    /// Name of the map, or name of value type if map is not named.
+    fn label(&self) -> &'static str {
+        self.name.unwrap_or_else(std::any::type_name::<T>)
+    }
+
+    /// Get entry corresponding to provided type, if it exists.
+    fn get_entry(&self, id: TypeId) -> Option<&'static Entry<T>> {
+        let reader = self.map.read().unwrap();
+        reader.get(&Key::new(id)).cloned() // Clone reference
+    }
+
+    /// Collect all entries visible to current thread.
+    ///
+    /// With `insertion-order` feature entries are listed in order values
+    /// were initialized by the map, followed by entries without such value.
+    /// Otherwise order is unspecified.
+    fn entries(&self) -> Vec<&'static Entry<T>> {
+        #[allow(unused_mut)]
+        let mut entries: Vec<_> = self
+            .map
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(key, _)| key.is_current())
+            .map(|(_, entry)| *entry)
+            .collect();
+        #[cfg(feature = "insertion-order")]
+        entries.sort_by_key(|entry| entry.order.get().map_or(usize::MAX, |order| *order));
+        entries
+    }
+}
+
+/// Key of entry in the map.
+///
+/// When test support is enabled, keys include isolation generation of the
+/// thread, see [`testing::isolate`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    id: TypeId,
+    #[cfg(any(test, feature = "testing"))]
+    generation: u64,
+}
+
+impl Key {
+    fn new(id: TypeId) -> Self {
+        Self {
+            id,
+            #[cfg(any(test, feature = "testing"))]
+            generation: testing::current_generation(),
+        }
+    }
+
+    /// Whether key belongs to isolation generation of current thread.
+    fn is_current(&self) -> bool {
+        #[cfg(any(test, feature = "testing"))]
+        return self.generation == testing::current_generation();
+        #[cfg(not(any(test, feature = "testing")))]
+        true
+    }
+}
+
+/// Hasher of keys.
+///
+/// `TypeId` is already a hash of the type, so there is no need to hash it
+/// again with SipHash. Written integers are only mixed together.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write_u64(u64::from(*byte));
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.write_u64(n as u64);
+        self.write_u64((n >> 64) as u64);
+    }
+}
+
+type KeyMap<V> = HashMap<Key, V, BuildHasherDefault<KeyHasher>>;
+
+/// Name of key type, which was only known by its `TypeId`.
+const UNKNOWN_TYPE_NAME: &str = "<unknown>";
+
+/// Storage of value corresponding to single type.
+struct Entry<T: 'static> {
+    cell: OnceCell<T>,
+    type_id: TypeId,
+    /// Name of key type. Unset while entry is only known by `TypeId`, and
+    /// set by the first access with the type itself.
+    type_name: OnceCell<&'static str>,
+    /// Number of values initialized by the map before this one, if value
+    /// was initialized by the map.
+    order: OnceCell<usize>,
+    /// Time, when initializer was started.
+    init_started: OnceCell<Instant>,
+    init_duration: OnceCell<Duration>,
+    /// Call site, which ran initializer.
+    init_location: OnceCell<&'static Location<'static>>,
+    /// Wall clock time, when initialization finished.
+    #[cfg(feature = "timestamps")]
+    init_time: OnceCell<std::time::SystemTime>,
+    /// Backtrace of first initialization.
+    #[cfg(feature = "init-trace")]
+    init_trace: OnceCell<std::backtrace::Backtrace>,
+    /// Nanoseconds since `access::EPOCH` plus one, or zero if never accessed.
+    #[cfg(feature = "access-time")]
+    last_access: AtomicU64,
+    /// Number of times value was returned by the map.
+    #[cfg(feature = "stats")]
+    reads: AtomicU64,
+    /// Set if initializer panicked.
+    poisoned: AtomicBool,
+    /// Names of types, whose initializers requested this entry.
+    dependents: Mutex<Vec<&'static str>>,
+    /// Values installed by `testing::override_for`, latest last.
+    #[cfg(any(test, feature = "testing"))]
+    overrides: RwLock<Vec<(testing::OverrideScope, &'static T)>>,
+}
+
+impl<T: 'static> Entry<T> {
+    fn new(type_id: TypeId, type_name: &'static str) -> Self {
+        let entry = Self {
+            cell: OnceCell::new(),
+            type_id,
+            type_name: OnceCell::new(),
+            order: OnceCell::new(),
+            init_started: OnceCell::new(),
+            init_duration: OnceCell::new(),
+            init_location: OnceCell::new(),
+            #[cfg(feature = "timestamps")]
+            init_time: OnceCell::new(),
+            #[cfg(feature = "init-trace")]
+            init_trace: OnceCell::new(),
+            #[cfg(feature = "access-time")]
+            last_access: AtomicU64::new(0),
+            #[cfg(feature = "stats")]
+            reads: AtomicU64::new(0),
+            poisoned: AtomicBool::new(false),
+            dependents: Mutex::new(Vec::new()),
+            #[cfg(any(test, feature = "testing"))]
+            overrides: RwLock::new(Vec::new()),
         };
-        cell
-            .get_or_init(f)
+        entry.learn_type_name(type_name);
+        entry
+    }
+
+    /// Name of key type, or `"<unknown>"` if entry was only accessed by
+    /// `TypeId`.
+    fn type_name(&self) -> &'static str {
+        self.type_name.get().copied().unwrap_or(UNKNOWN_TYPE_NAME)
+    }
+
+    /// Remember name of key type, if it was not known yet.
+    fn learn_type_name(&self, type_name: &'static str) {
+        if type_name != UNKNOWN_TYPE_NAME {
+            let _ = self.type_name.set(type_name);
+        }
+    }
+
+    fn add_dependent(&self, dependent: &'static str) {
+        let mut dependents = self.dependents.lock().unwrap();
+        if !dependents.contains(&dependent) {
+            dependents.push(dependent);
+        }
+    }
+
+    /// Panic if entry was created for type with different name, which
+    /// means that `TypeId`s of distinct types collided, for example across
+    /// dynamic library boundary.
+    #[cfg(debug_assertions)]
+    fn check_type_name(&self, type_name: &'static str, map: &str) {
+        let known = |name| name != UNKNOWN_TYPE_NAME;
+        if known(type_name) && known(self.type_name()) && type_name != self.type_name() {
+            panic!(
+                "TypeId collision in map `{}`: `{}` and `{}` have the same TypeId",
+                map,
+                self.type_name(),
+                type_name
+            );
+        }
+    }
+}
+
+/// Marks entry as poisoned if dropped during panic.
+struct PoisonOnPanic<'a>(&'a AtomicBool);
+
+impl Drop for PoisonOnPanic<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.store(true, Ordering::Release);
+        }
     }
 }
 
-impl<T: 'static> Default for StaticTypeMap<T> {
+/// Run initializer for value corresponding to type named `key`.
+fn initialize<T, Init>(map: &'static str, key: &'static str, f: Init) -> T
+where
+    Init: FnOnce() -> T,
+{
+    let f = move || debug::initialize(map, key, f);
+    #[cfg(feature = "tracing")]
+    let f = move || trace::initialize(map, key, f);
+    #[cfg(feature = "metrics")]
+    let f = move || metric::initialize(map, key, f);
+    f()
+}
+
+impl<T: 'static, NS: 'static> fmt::Debug for StaticTypeMap<T, NS> {
+    /// Lists name of the map and names of key types of initialized values,
+    /// sorted by name, or in insertion order with `insertion-order` feature.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[allow(unused_mut)]
+        let mut entries: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.cell.get().is_some())
+            .map(|entry| entry.type_name())
+            .collect();
+        #[cfg(not(feature = "insertion-order"))]
+        entries.sort_unstable();
+        f.debug_struct("StaticTypeMap")
+            .field("name", &self.name)
+            .field("entries", &entries)
+            .finish()
+    }
+}
+
+impl<T: 'static, NS: 'static> Default for StaticTypeMap<T, NS> {
     fn default() -> Self {
         Self::new()
     }
@@ -154,6 +885,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn deadlock_issue4() {
         fn map() -> &'static StaticTypeMap<String> {
             static VALUE: OnceCell<StaticTypeMap<String>> = OnceCell::new();
@@ -168,4 +900,129 @@ mod tests {
 
         assert_eq!(res, "u32 and")
     }
+
+    #[test]
+    fn different_types_do_not_block_each_other() {
+        use std::sync::mpsc::channel;
+        use std::thread;
+        use std::time::Duration;
+
+        static MAP: once_cell::sync::Lazy<StaticTypeMap<u32>> =
+            once_cell::sync::Lazy::new(StaticTypeMap::new);
+
+        let (started, wait_started) = channel();
+        let (finished, wait_finished) = channel();
+        // Initializer of `u8` only finishes after `u16` was initialized on
+        // another thread.
+        let slow = thread::spawn(move || {
+            *MAP.call_once::<u8, _>(|| {
+                started.send(()).unwrap();
+                wait_finished
+                    .recv_timeout(Duration::from_secs(10))
+                    .expect("initialization of u16 was blocked by u8");
+                8
+            })
+        });
+        wait_started.recv().unwrap();
+        let fast = thread::spawn(|| *MAP.call_once::<u16, _>(|| 16));
+        assert_eq!(fast.join().unwrap(), 16);
+        finished.send(()).unwrap();
+
+        assert_eq!(slow.join().unwrap(), 8);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "TypeId collision in map `u32`: `u8` and `fake::u8`")]
+    fn type_id_collision_panics() {
+        static MAP: once_cell::sync::Lazy<StaticTypeMap<u32>> =
+            once_cell::sync::Lazy::new(StaticTypeMap::new);
+
+        MAP.call_once::<u8, _>(|| 8);
+        MAP.entry_by_id(TypeId::of::<u8>(), "fake::u8");
+    }
+
+    #[test]
+    #[should_panic(expected = "reentrant initialization of `u8` in `StaticTypeMap<u32>`")]
+    fn reentrant_initialization_panics() {
+        static MAP: once_cell::sync::Lazy<StaticTypeMap<u32>> =
+            once_cell::sync::Lazy::new(StaticTypeMap::new);
+
+        MAP.call_once::<u8, _>(|| *MAP.call_once::<u8, _>(|| 1) + 1);
+    }
+
+    #[test]
+    fn initializer_panic_poisons_waiters() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::sync::mpsc::channel;
+        use std::thread;
+        use std::time::Duration;
+
+        static MAP: once_cell::sync::Lazy<StaticTypeMap<u32>> =
+            once_cell::sync::Lazy::new(StaticTypeMap::new);
+
+        let (started, wait_started) = channel();
+        let first = thread::spawn(move || {
+            MAP.call_once::<u8, _>(|| {
+                started.send(()).unwrap();
+                thread::sleep(Duration::from_millis(50));
+                panic!("first attempt failed")
+            });
+        });
+        wait_started.recv().unwrap();
+        let waiter = thread::spawn(|| *MAP.call_once::<u8, _>(|| 1));
+
+        assert!(first.join().is_err());
+        let message = waiter.join().unwrap_err();
+        assert_eq!(
+            message.downcast_ref::<String>().unwrap(),
+            "initializer of `u8` panicked, value is poisoned"
+        );
+        let later = catch_unwind(AssertUnwindSafe(|| *MAP.call_once::<u8, _>(|| 1)));
+        assert!(later.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "insertion-order")]
+    fn debug_lists_insertion_order() {
+        static MAP: once_cell::sync::Lazy<StaticTypeMap<u32>> =
+            once_cell::sync::Lazy::new(StaticTypeMap::new);
+
+        // Order of initialization, not of entry creation, is kept
+        MAP.cell_of::<u8>();
+        MAP.call_once::<u16, _>(|| 16);
+        MAP.call_once::<u8, _>(|| 8);
+        assert_eq!(
+            format!("{:?}", *MAP),
+            r#"StaticTypeMap { name: None, entries: ["u16", "u8"] }"#
+        );
+    }
+
+    #[test]
+    fn generic_lazy_per_type() {
+        generic_lazy!(static NAME<T: std::fmt::Debug + Default>: String = format!("{:?}", T::default()));
+
+        assert_eq!(NAME::<u32>(), "0");
+        assert_eq!(NAME::<String>(), "\"\"");
+        assert_eq!(NAME::<u32>() as *const String, NAME::<u32>() as *const String);
+    }
+
+    #[test]
+    fn generic_lazy_bounds() {
+        generic_lazy! {
+            static BYTE<T: Into<u8> + Default>: u8 = T::default().into();
+            static SIZE<T: ?Sized + 'static>: usize = std::mem::size_of::<&T>();
+            static DEBUG<T: ::std::fmt::Debug + Default + PartialEq<Option<Vec<u8>>>>: String =
+                format!("{:?}", T::default())
+        }
+        generic_lazy_static! {
+            static ref WIDE<T: Into<Option<u64>> + Default>: bool = T::default().into().is_some();
+        }
+
+        assert_eq!(*BYTE::<bool>(), 0);
+        assert_eq!(*SIZE::<str>(), 2 * std::mem::size_of::<usize>());
+        assert_eq!(*SIZE::<u8>(), std::mem::size_of::<usize>());
+        assert_eq!(DEBUG::<Option<Vec<u8>>>(), "None");
+        assert!(WIDE::<u64>());
+    }
 }
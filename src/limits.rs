@@ -0,0 +1,147 @@
+use std::mem::size_of;
+use std::sync::Arc;
+
+use crate::{Entry, StaticTypeMap};
+
+pub(crate) type LimitHook = Arc<dyn Fn(&'static str, usize) + Send + Sync>;
+pub(crate) type BudgetHook = Arc<dyn Fn(usize) + Send + Sync>;
+
+pub(crate) struct EntryLimit {
+    max: usize,
+    hook: LimitHook,
+}
+
+pub(crate) struct LeakBudget {
+    bytes: usize,
+    hook: BudgetHook,
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Refuse to create more than `max` entries, calling `hook` with key
+    /// type name and number of entries it would make, whenever entry is
+    /// refused.
+    ///
+    /// Guard rail against generic code accidentally instantiated for
+    /// unbounded number of types, each of them leaking its entry. Refused
+    /// entry is not allocated: [`call_once`](Self::call_once) panics, and
+    /// [`try_call_once`](Self::try_call_once) returns
+    /// [`Error::CapacityExceeded`](crate::Error::CapacityExceeded). Hook can
+    /// log or report metric. Replaces previously set limit.
+    ///
+    /// ```
+    /// use generic_static::{Error, StaticTypeMap};
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.set_max_entries(1, |type_name, entries| {
+    ///     eprintln!("`{}` would be entry #{} of the map", type_name, entries);
+    /// });
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// let full = MAP.try_call_once::<u16, _>(|| 16);
+    /// assert_eq!(full, Err(Error::CapacityExceeded { type_name: "u16", max: 1 }));
+    /// assert_eq!(MAP.leaked_entries(), 1);
+    /// ```
+    pub fn set_max_entries<Hook>(&self, max: usize, hook: Hook)
+    where
+        Hook: Fn(&'static str, usize) + Send + Sync + 'static,
+    {
+        *self.entry_limit.write().unwrap() = Some(EntryLimit {
+            max,
+            hook: Arc::new(hook),
+        });
+    }
+
+    /// Call `hook` with number of bytes leaked by the map, whenever entry
+    /// is created while leaked storage exceeds `bytes`.
+    ///
+    /// Leaked storage is measured same as by
+    /// [`bytes_leaked`](Self::bytes_leaked). Hook can log, report metric,
+    /// or panic. Replaces previously set budget.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<[u8; 1024]>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.set_leak_budget(1 << 20, |leaked| {
+    ///     eprintln!("map leaked {} bytes, budget is 1 MiB", leaked);
+    /// });
+    /// MAP.call_once::<u8, _>(|| [0; 1024]);
+    /// ```
+    pub fn set_leak_budget<Hook>(&self, bytes: usize, hook: Hook)
+    where
+        Hook: Fn(usize) + Send + Sync + 'static,
+    {
+        *self.leak_budget.write().unwrap() = Some(LeakBudget {
+            bytes,
+            hook: Arc::new(hook),
+        });
+    }
+
+    /// Limit set with `set_max_entries`, and its hook.
+    pub(crate) fn entry_limit(&self) -> Option<(usize, LimitHook)> {
+        self.entry_limit
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|limit| (limit.max, limit.hook.clone()))
+    }
+
+    /// Call budget hook if it is exceeded. Called after entry was created,
+    /// without holding lock of the map.
+    pub(crate) fn check_limits(&self, entries: usize) {
+        let leaked = entries * size_of::<Entry<T>>();
+        let hook = match &*self.leak_budget.read().unwrap() {
+            Some(budget) if leaked > budget.bytes => Some(budget.hook.clone()),
+            _ => None,
+        };
+        if let Some(hook) = hook {
+            hook(leaked);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::panic::catch_unwind;
+    use std::sync::Mutex;
+
+    #[test]
+    fn entries_beyond_limit_are_refused() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::named("limited"));
+        static EXCEEDED: Mutex<Vec<(&str, usize)>> = Mutex::new(Vec::new());
+        MAP.set_max_entries(2, |type_name, entries| {
+            EXCEEDED.lock().unwrap().push((type_name, entries))
+        });
+
+        MAP.call_once::<u8, _>(|| 8);
+        MAP.call_once::<u16, _>(|| 16);
+        let err = catch_unwind(|| MAP.call_once::<u32, _>(|| 32)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            "can not create entry of `u32`, map `limited` is limited to 2 entries"
+        );
+        assert!(MAP.try_call_once::<u32, _>(|| 32).is_err());
+        assert_eq!(*EXCEEDED.lock().unwrap(), [("u32", 3), ("u32", 3)]);
+        assert_eq!(MAP.leaked_entries(), 2);
+        assert_eq!(*MAP.call_once::<u16, _>(|| unreachable!()), 16);
+    }
+
+    #[test]
+    fn budget_hook_is_called_once_exceeded() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        static LEAKED: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+        let entry = size_of::<Entry<u32>>();
+        MAP.set_leak_budget(entry, |leaked| LEAKED.lock().unwrap().push(leaked));
+
+        MAP.call_once::<u8, _>(|| 8);
+        assert!(LEAKED.lock().unwrap().is_empty());
+        MAP.call_once::<u16, _>(|| 16);
+        assert_eq!(*LEAKED.lock().unwrap(), [2 * entry]);
+        assert_eq!(MAP.bytes_leaked(), 2 * entry);
+    }
+}
@@ -0,0 +1,98 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::{Entry, Key, StaticTypeMap};
+
+thread_local! {
+    /// Entries of all maps, previously accessed from current thread, keyed
+    /// by map address.
+    static CACHE: RefCell<HashMap<(usize, Key), &'static dyn Any>> = RefCell::new(HashMap::new());
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Same as [`StaticTypeMap::call_once`], but looks up entry in thread
+    /// local cache first.
+    ///
+    /// Once entry was accessed from a thread, subsequent lookups from the
+    /// same thread do not touch the map lock, which avoids contention on
+    /// very hot read paths shared by many threads. Value is still
+    /// initialized once per process.
+    ///
+    /// Lookups served by the cache do not write to the map or the entry, so
+    /// they are not counted as hits or reads, and with `access-time` feature
+    /// do not update time of last access.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    /// static CALLS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// let threads: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         std::thread::spawn(|| {
+    ///             *MAP.call_once_local::<u8, _>(|| {
+    ///                 CALLS.fetch_add(1, Ordering::Relaxed);
+    ///                 8
+    ///             })
+    ///         })
+    ///     })
+    ///     .collect();
+    /// for thread in threads {
+    ///     assert_eq!(thread.join().unwrap(), 8);
+    /// }
+    /// assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    /// ```
+    #[track_caller]
+    pub fn call_once_local<Type, Init>(&'static self, f: Init) -> &'static T
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        let key = (self as *const Self as usize, Key::new(TypeId::of::<Type>()));
+        let cached = CACHE
+            .with(|cache| cache.borrow().get(&key).cloned())
+            .and_then(|entry| entry.downcast_ref::<Entry<T>>());
+        if let Some(entry) = cached {
+            #[cfg(any(test, feature = "testing"))]
+            if let Some(value) = entry.current_override() {
+                return value;
+            }
+            // Only initialized entries are cached
+            if let Some(value) = entry.cell.get() {
+                return value;
+            }
+        }
+        let map = self.owner(TypeId::of::<Type>());
+        let entry = map.entry::<Type>();
+        let value = map.get_or_init(entry, f);
+        // Only own initialized values are cached, since delegation to parent
+        // changes once child gets value of its own
+        if std::ptr::eq(map, self) && entry.cell.get().is_some() {
+            CACHE.with(|cache| cache.borrow_mut().insert(key, entry));
+        }
+        value
+    }
+}
+
+#[cfg(all(test, feature = "stats"))]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn cached_lookups_do_not_touch_counters() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        assert_eq!(*MAP.call_once_local::<u8, _>(|| 8), 8);
+        let (hits, misses) = (MAP.hits(), MAP.misses());
+
+        for _ in 0..3 {
+            assert_eq!(*MAP.call_once_local::<u8, _>(|| unreachable!()), 8);
+        }
+        assert_eq!((MAP.hits(), MAP.misses()), (hits, misses));
+        assert_eq!(MAP.validate(), Ok(()));
+    }
+}
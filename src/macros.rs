@@ -0,0 +1,163 @@
+/// Declare a generic lazily initialized value.
+///
+/// Works like `Lazy`, but the value is initialized separately for every
+/// type the accessor is called with. Initializer is specified once at the
+/// declaration, so call sites do not need to supply a closure.
+///
+/// Declaration expands into a function, which returns `&'static` reference
+/// to value corresponding to provided type. Value type can not depend on
+/// type parameter, same as with any other static declared inside of generic
+/// function.
+///
+/// ```
+/// use generic_static::generic_lazy;
+///
+/// trait Name {
+///     fn name() -> &'static str;
+/// }
+///
+/// struct A;
+/// impl Name for A {
+///     fn name() -> &'static str {
+///         "A"
+///     }
+/// }
+///
+/// struct B;
+/// impl Name for B {
+///     fn name() -> &'static str {
+///         "B"
+///     }
+/// }
+///
+/// generic_lazy! {
+///     static GREETING<T: Name>: String = format!("Hello, {}!", T::name());
+/// }
+///
+/// assert_eq!(GREETING::<A>(), "Hello, A!");
+/// assert_eq!(GREETING::<B>(), "Hello, B!");
+/// ```
+#[macro_export]
+macro_rules! generic_lazy {
+    () => {};
+    // Bounds are collected token by token, until `>` closing generics.
+    // Depth holds one `<` per unclosed angle bracket of bounds.
+    (@bounds $next:tt $head:tt [$($bound:tt)*] [] > : $value:ty = $init:expr $(; $($rest:tt)*)?) => {
+        $crate::generic_lazy!(@expand $head [$($bound)*] $value, $init);
+        $crate::generic_lazy!(@next $next $($($rest)*)?);
+    };
+    (@bounds $next:tt $head:tt [$($bound:tt)*] [<] >> : $value:ty = $init:expr $(; $($rest:tt)*)?) => {
+        $crate::generic_lazy!(@expand $head [$($bound)* >] $value, $init);
+        $crate::generic_lazy!(@next $next $($($rest)*)?);
+    };
+    (@bounds $next:tt $head:tt [] [] : $($rest:tt)*) => {
+        $crate::generic_lazy!(@bounds $next $head [] [] $($rest)*);
+    };
+    (@bounds $next:tt $head:tt [$($bound:tt)*] [$($depth:tt)*] < $($rest:tt)*) => {
+        $crate::generic_lazy!(@bounds $next $head [$($bound)* <] [$($depth)* <] $($rest)*);
+    };
+    (@bounds $next:tt $head:tt [$($bound:tt)*] [< $($depth:tt)*] > $($rest:tt)*) => {
+        $crate::generic_lazy!(@bounds $next $head [$($bound)* >] [$($depth)*] $($rest)*);
+    };
+    (@bounds $next:tt $head:tt [$($bound:tt)*] [< < $($depth:tt)*] >> $($rest:tt)*) => {
+        $crate::generic_lazy!(@bounds $next $head [$($bound)* >>] [$($depth)*] $($rest)*);
+    };
+    (@bounds $next:tt $head:tt [$($bound:tt)*] $depth:tt $token:tt $($rest:tt)*) => {
+        $crate::generic_lazy!(@bounds $next $head [$($bound)* $token] $depth $($rest)*);
+    };
+    (@expand [[$($attr:tt)*] [$($vis:tt)*] $name:ident $param:ident] [$($bound:tt)*] $value:ty, $init:expr) => {
+        $($attr)*
+        #[allow(non_snake_case)]
+        $($vis)* fn $name<$param>() -> &'static $value
+        where
+            $param: 'static,
+            $param: $($bound)*,
+        {
+            static MAP: $crate::__private::OnceCell<$crate::StaticTypeMap<$value>> =
+                $crate::__private::OnceCell::new();
+            MAP.get_or_init($crate::StaticTypeMap::new)
+                .call_once::<$crate::__private::PhantomData<$param>, _>(|| $init)
+        }
+    };
+    (@next generic_lazy $($rest:tt)*) => {
+        $crate::generic_lazy!($($rest)*);
+    };
+    (@next generic_lazy_static $($rest:tt)*) => {
+        $crate::generic_lazy_static!($($rest)*);
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis static $name:ident<$param:ident $($rest:tt)*
+    ) => {
+        $crate::generic_lazy!(@bounds generic_lazy [[$(#[$attr])*] [$vis] $name $param] [] [] $($rest)*);
+    };
+}
+
+/// Declare generic lazily initialized values using `lazy_static!` syntax.
+///
+/// Compatibility shim for codebases using `lazy_static`. Each declaration
+/// expands the same way as with [`generic_lazy!`], so values are accessed
+/// by calling declared name with type parameter.
+///
+/// ```
+/// use generic_static::generic_lazy_static;
+///
+/// generic_lazy_static! {
+///     static ref SIZE<T>: usize = std::mem::size_of::<T>();
+///     pub static ref NAME<T: Default + std::fmt::Debug>: String = format!("{:?}", T::default());
+/// }
+///
+/// assert_eq!(*SIZE::<u32>(), 4);
+/// assert_eq!(NAME::<u8>(), "0");
+/// ```
+#[macro_export]
+macro_rules! generic_lazy_static {
+    () => {};
+    (
+        $(#[$attr:meta])*
+        $vis:vis static ref $name:ident<$param:ident $($rest:tt)*
+    ) => {
+        $crate::generic_lazy!(@bounds generic_lazy_static [[$(#[$attr])*] [$vis] $name $param] [] [] $($rest)*);
+    };
+}
+
+/// Declare accessor function of static map.
+///
+/// Expands to function returning `&'static` reference to the map, stored in
+/// `static` item and created on first call. Map is created with `new`, or
+/// with provided expression.
+///
+/// ```
+/// use generic_static::{static_type_map, StaticTypeMap};
+///
+/// static_type_map! {
+///     fn names() -> StaticTypeMap<String>;
+///     pub fn sizes() -> StaticTypeMap<usize> = StaticTypeMap::named("sizes");
+/// }
+///
+/// assert_eq!(names().call_once::<u8, _>(|| "u8".to_string()), "u8");
+/// assert_eq!(sizes().name(), Some("sizes"));
+/// ```
+#[macro_export]
+macro_rules! static_type_map {
+    () => {};
+    (
+        $(#[$attr:meta])*
+        $vis:vis fn $name:ident() -> $map:ty $(= $init:expr)?;
+        $($rest:tt)*
+    ) => {
+        $(#[$attr])*
+        $vis fn $name() -> &'static $map {
+            static MAP: $crate::__private::OnceCell<$map> = $crate::__private::OnceCell::new();
+            MAP.get_or_init(|| $crate::static_type_map!(@init $map $(, $init)?))
+        }
+
+        $crate::static_type_map!($($rest)*);
+    };
+    (@init $map:ty) => {
+        <$map>::new()
+    };
+    (@init $map:ty, $init:expr) => {
+        $init
+    };
+}
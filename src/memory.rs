@@ -0,0 +1,244 @@
+use std::fmt;
+use std::mem::size_of;
+
+use crate::{Entry, Key, StaticTypeMap};
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Number of entries allocated by the map.
+    ///
+    /// Entries are never freed, so it is also number of leaked allocations.
+    pub fn leaked_entries(&self) -> usize {
+        self.map.read().unwrap().len()
+    }
+
+    /// Number of bytes permanently leaked by entries of the map.
+    ///
+    /// Includes inline size of stored values, but not memory owned by them.
+    pub fn bytes_leaked(&self) -> usize {
+        self.leaked_entries() * size_of::<Entry<T>>()
+    }
+
+    /// Number of bytes used by the map, including both leaked entries and
+    /// lookup table.
+    pub fn memory_usage(&self) -> usize {
+        let table = self.map.read().unwrap().capacity() * size_of::<(Key, &Entry<T>)>();
+        self.bytes_leaked() + table
+    }
+
+    /// Same as [`StaticTypeMap::memory_usage`], but also includes heap
+    /// memory owned by initialized values.
+    #[cfg(feature = "heap-size")]
+    pub fn deep_memory_usage(&self) -> usize
+    where
+        T: HeapSize,
+    {
+        let owned: usize = self
+            .map
+            .read()
+            .unwrap()
+            .values()
+            .filter_map(|entry| entry.cell.get())
+            .map(HeapSize::heap_size)
+            .sum();
+        self.memory_usage() + owned
+    }
+
+    /// Describe allocations intentionally leaked by the map.
+    ///
+    /// Report can be rendered as LeakSanitizer or Valgrind suppressions, so
+    /// intentional leaks do not hide real ones in CI.
+    pub fn leak_report(&self) -> LeakReport {
+        let mut entries: Vec<_> = self
+            .map
+            .read()
+            .unwrap()
+            .values()
+            .map(|entry| LeakedEntry {
+                type_name: entry.type_name(),
+                address: *entry as *const Entry<T> as usize,
+                size: size_of::<Entry<T>>(),
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.address);
+        LeakReport { entries }
+    }
+}
+
+/// Allocate value, which should stay alive until program terminates.
+///
+/// All intentional leaks of the crate are allocated by `intentional_leak_*`
+/// functions, so they can be suppressed by name of the allocating frame.
+/// Allocation must happen inside of them, since sanitizers match the stack
+/// of allocation, not of the leak.
+#[inline(never)]
+pub(crate) fn intentional_leak_new<V>(value: V) -> &'static mut V {
+    Box::leak(Box::new(value))
+}
+
+/// Same as `intentional_leak_new`, but copies string into leaked allocation.
+#[inline(never)]
+pub(crate) fn intentional_leak_str(s: &str) -> &'static str {
+    Box::leak(s.into())
+}
+
+/// Same as `intentional_leak_new`, but moves items into leaked allocation.
+#[inline(never)]
+pub(crate) fn intentional_leak_slice<V>(items: Vec<V>) -> &'static [V] {
+    // Reallocate, so allocation is not attributed to pushes of the caller
+    let mut leaked = Vec::with_capacity(items.len());
+    leaked.extend(items);
+    Box::leak(leaked.into_boxed_slice())
+}
+
+/// Common prefix of names of functions allocating intentional leaks.
+/// Suppressions match function names by substring.
+const LEAK_FUNCTION: &str = "generic_static::memory::intentional_leak";
+
+/// Single allocation leaked by map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakedEntry {
+    /// Name of key type, which entry belongs to.
+    pub type_name: &'static str,
+    pub address: usize,
+    pub size: usize,
+}
+
+/// Allocations intentionally leaked by map, see
+/// [`StaticTypeMap::leak_report`].
+///
+/// Displayed as LeakSanitizer suppression file, listing leaked entries in
+/// comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LeakReport {
+    pub entries: Vec<LeakedEntry>,
+}
+
+impl LeakReport {
+    /// Total number of leaked bytes.
+    pub fn total_size(&self) -> usize {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+
+    /// Suppression in LeakSanitizer format (`LSAN_OPTIONS=suppressions=...`).
+    pub fn lsan_suppressions(&self) -> String {
+        format!("leak:{}\n", LEAK_FUNCTION)
+    }
+
+    /// Suppression in Valgrind format (`--suppressions=...`).
+    pub fn valgrind_suppressions(&self) -> String {
+        format!(
+            "{{\n   generic_static_intentional_leak\n   Memcheck:Leak\n   match-leak-kinds: all\n   ...\n   fun:*{}*\n}}\n",
+            LEAK_FUNCTION.replace("::", "*")
+        )
+    }
+}
+
+impl fmt::Display for LeakReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "# generic_static: {} intentional leaks, {} bytes",
+            self.entries.len(),
+            self.total_size()
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "#   {:#x} ({} bytes): {}",
+                entry.address, entry.size, entry.type_name
+            )?;
+        }
+        f.write_str(&self.lsan_suppressions())
+    }
+}
+
+/// Values, which are able to report amount of heap memory they own.
+#[cfg(feature = "heap-size")]
+pub trait HeapSize {
+    /// Number of heap allocated bytes owned by value, excluding
+    /// `size_of_val(self)`.
+    fn heap_size(&self) -> usize;
+}
+
+#[cfg(feature = "heap-size")]
+mod impls {
+    use super::HeapSize;
+    use std::mem::size_of;
+
+    macro_rules! impl_no_heap {
+        ($($ty:ty),*) => {
+            $(
+                impl HeapSize for $ty {
+                    fn heap_size(&self) -> usize {
+                        0
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_no_heap!(
+        (), bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64,
+        &'static str
+    );
+
+    impl HeapSize for String {
+        fn heap_size(&self) -> usize {
+            self.capacity()
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Vec<T> {
+        fn heap_size(&self) -> usize {
+            self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Box<T> {
+        fn heap_size(&self) -> usize {
+            size_of::<T>() + (**self).heap_size()
+        }
+    }
+
+    impl<T: HeapSize> HeapSize for Option<T> {
+        fn heap_size(&self) -> usize {
+            self.as_ref().map_or(0, HeapSize::heap_size)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn leaked_bytes_grow_with_entries() {
+        static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        assert_eq!(MAP.bytes_leaked(), 0);
+
+        MAP.call_once::<u8, _>(|| "u8".to_string());
+        MAP.call_once::<u16, _>(|| "u16".to_string());
+
+        assert_eq!(MAP.leaked_entries(), 2);
+        assert_eq!(MAP.bytes_leaked(), 2 * size_of::<Entry<String>>());
+        assert!(MAP.memory_usage() > MAP.bytes_leaked());
+        #[cfg(feature = "heap-size")]
+        assert!(MAP.deep_memory_usage() >= MAP.memory_usage() + 5);
+    }
+
+    #[test]
+    fn leak_report_lists_entries() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| 1);
+
+        let report = MAP.leak_report();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].type_name, "u8");
+        assert_eq!(report.total_size(), MAP.bytes_leaked());
+
+        let text = report.to_string();
+        assert!(text.contains("u8"));
+        assert!(text.ends_with("leak:generic_static::memory::intentional_leak\n"));
+    }
+}
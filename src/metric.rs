@@ -0,0 +1,30 @@
+use std::time::Instant;
+
+/// Record creation of new entry in map.
+pub(crate) fn entry_created(map: &'static str, key: &'static str) {
+    ::metrics::counter!(
+        "generic_static_entries_created",
+        "map" => map,
+        "key" => key,
+    )
+    .increment(1);
+}
+
+/// Run initializer, recording number of initializations and their
+/// duration.
+pub(crate) fn initialize<T, Init>(map: &'static str, key: &'static str, f: Init) -> T
+where
+    Init: FnOnce() -> T,
+{
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    let labels = [
+        ("map", map),
+        ("key", key),
+    ];
+    ::metrics::counter!("generic_static_initializations", &labels).increment(1);
+    ::metrics::histogram!("generic_static_init_duration_seconds", &labels)
+        .record(elapsed.as_secs_f64());
+    value
+}
@@ -0,0 +1,108 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use once_cell::sync::OnceCell;
+
+use crate::memory;
+
+/// Map of static values, holding several named values per type.
+///
+/// Works like [`StaticTypeMap`](crate::StaticTypeMap), but each value is
+/// identified by both key type and name.
+///
+/// ```
+/// use generic_static::StaticTypeMultiMap;
+/// use once_cell::sync::Lazy;
+///
+/// static MAP: Lazy<StaticTypeMultiMap<String>> = Lazy::new(StaticTypeMultiMap::new);
+///
+/// fn artifacts<T: 'static>() -> (&'static str, &'static str) {
+///     let name = std::any::type_name::<T>();
+///     (
+///         MAP.call_once_named::<T, _>("primary", || format!("{} primary", name)),
+///         MAP.call_once_named::<T, _>("fallback", || format!("{} fallback", name)),
+///     )
+/// }
+///
+/// assert_eq!(artifacts::<u8>(), ("u8 primary", "u8 fallback"));
+/// assert_eq!(MAP.names::<u8>(), ["fallback", "primary"]);
+/// ```
+pub struct StaticTypeMultiMap<T: 'static> {
+    map: RwLock<HashMap<TypeId, HashMap<&'static str, &'static OnceCell<T>>>>,
+}
+
+impl<T: 'static> StaticTypeMultiMap<T> {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Initialize static value corresponding to provided type and name.
+    ///
+    /// Initialized value will stay on heap until program terminated.
+    /// No drop method will be called.
+    pub fn call_once_named<Type, Init>(&'static self, name: &'static str, f: Init) -> &'static T
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        let id = TypeId::of::<Type>();
+        let cell = self.get_cell(id, name);
+        let cell = match cell {
+            Some(cell) => cell,
+            None => *self
+                .map
+                .write()
+                .unwrap()
+                .entry(id)
+                .or_default()
+                .entry(name)
+                .or_insert_with(|| memory::intentional_leak_new(OnceCell::new())),
+        };
+        cell.get_or_init(f)
+    }
+
+    /// Get initialized value corresponding to provided type and name.
+    pub fn get_named<Type>(&self, name: &str) -> Option<&'static T>
+    where
+        Type: 'static,
+    {
+        self.get_cell(TypeId::of::<Type>(), name)
+            .and_then(|cell| cell.get())
+    }
+
+    /// Names of values present for provided type, in sorted order.
+    pub fn names<Type>(&self) -> Vec<&'static str>
+    where
+        Type: 'static,
+    {
+        let mut names: Vec<_> = self
+            .map
+            .read()
+            .unwrap()
+            .get(&TypeId::of::<Type>())
+            .into_iter()
+            .flatten()
+            .filter(|(_, cell)| cell.get().is_some())
+            .map(|(name, _)| *name)
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn get_cell(&self, id: TypeId, name: &str) -> Option<&'static OnceCell<T>> {
+        self.map
+            .read()
+            .unwrap()
+            .get(&id)
+            .and_then(|cells| cells.get(name).cloned())
+    }
+}
+
+impl<T: 'static> Default for StaticTypeMultiMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,84 @@
+use once_cell::sync::Lazy;
+
+use crate::InlineTypeMap;
+
+/// Per-type counterpart of `std::sync::Once`.
+///
+/// Runs side-effecting setup, such as registering type with FFI library or
+/// schema registry, exactly once per type. Nothing is stored except marker,
+/// that type is done, so unlike `StaticTypeMap<()>` used as such gate, no
+/// entry is allocated per type.
+///
+/// ```
+/// use generic_static::TypeOnce;
+///
+/// static REGISTER: TypeOnce = TypeOnce::new();
+///
+/// fn register<T: 'static>() {
+///     REGISTER.call_once::<T, _>(|| println!("registering {}", std::any::type_name::<T>()));
+/// }
+///
+/// register::<u8>();
+/// register::<u8>();
+/// assert!(REGISTER.is_completed::<u8>());
+/// assert!(!REGISTER.is_completed::<u16>());
+/// ```
+pub struct TypeOnce {
+    done: Lazy<InlineTypeMap<()>>,
+}
+
+impl TypeOnce {
+    pub const fn new() -> Self {
+        Self {
+            done: Lazy::new(InlineTypeMap::new),
+        }
+    }
+
+    /// Run `f` if it was not run for provided type yet. Returns whether `f`
+    /// was run by this call.
+    ///
+    /// Concurrent calls for the same type wait until `f` returns. If `f`
+    /// panics, type is not marked as done and next call runs it again.
+    pub fn call_once<Type, Init>(&self, f: Init) -> bool
+    where
+        Type: 'static,
+        Init: FnOnce(),
+    {
+        let mut ran = false;
+        self.done.call_once::<Type, _>(|| {
+            ran = true;
+            f()
+        });
+        ran
+    }
+
+    /// Whether setup for provided type was completed.
+    pub fn is_completed<Type>(&self) -> bool
+    where
+        Type: 'static,
+    {
+        self.done.get::<Type>().is_some()
+    }
+}
+
+impl Default for TypeOnce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn panicked_setup_is_retried() {
+        static ONCE: TypeOnce = TypeOnce::new();
+
+        assert!(catch_unwind(|| ONCE.call_once::<u8, _>(|| panic!("transient"))).is_err());
+        assert!(!ONCE.is_completed::<u8>());
+        assert!(ONCE.call_once::<u8, _>(|| ()));
+        assert!(!ONCE.call_once::<u8, _>(|| unreachable!()));
+    }
+}
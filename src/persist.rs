@@ -0,0 +1,295 @@
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::hash::fnv1a;
+use crate::StaticTypeMap;
+
+/// Number of temporary files created by this process, making their names
+/// unique across caches and threads sharing directory.
+static TMP_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// How long `load_or_init` waits for lock held by other process, before
+/// computing value without it.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval of checking whether lock held by other process was released.
+const LOCK_POLL: Duration = Duration::from_millis(10);
+
+/// Lock file of single type, removed when dropped.
+struct LockFile(PathBuf);
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// Directory backed cache of map values, shared across process restarts.
+///
+/// Values are stored one file per key type, prefixed with cache version.
+/// Files written with different version are ignored and overwritten, so
+/// bumping version invalidates whole cache. Encoding is provided by user,
+/// and IO errors are treated same as cache misses.
+///
+/// ```
+/// use generic_static::{DiskCache, StaticTypeMap};
+/// use once_cell::sync::Lazy;
+/// use std::convert::TryInto;
+///
+/// static MAP: Lazy<StaticTypeMap<u64>> = Lazy::new(StaticTypeMap::new);
+/// static CACHE: Lazy<DiskCache<u64>> = Lazy::new(|| {
+///     let dir = std::env::temp_dir().join("generic_static_doc_cache");
+///     DiskCache::new(
+///         dir,
+///         1,
+///         |value| value.to_le_bytes().to_vec(),
+///         |bytes| Some(u64::from_le_bytes(bytes.try_into().ok()?)),
+///     )
+/// });
+///
+/// CACHE.invalidate::<u8>().unwrap();
+/// assert_eq!(*MAP.call_once_cached::<u8, _>(&CACHE, || 8), 8);
+/// assert_eq!(CACHE.load::<u8>(), Some(8));
+/// ```
+pub struct DiskCache<T> {
+    dir: PathBuf,
+    version: u64,
+    encode: fn(&T) -> Vec<u8>,
+    decode: fn(&[u8]) -> Option<T>,
+}
+
+impl<T> DiskCache<T> {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        version: u64,
+        encode: fn(&T) -> Vec<u8>,
+        decode: fn(&[u8]) -> Option<T>,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            version,
+            encode,
+            decode,
+        }
+    }
+
+    /// Directory cache files are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Read cached value of `Type`, if present and written by the same
+    /// cache version.
+    pub fn load<Type: 'static>(&self) -> Option<T> {
+        let bytes = fs::read(self.path::<Type>()).ok()?;
+        if bytes.len() < 8 || bytes[..8] != self.version.to_le_bytes() {
+            return None;
+        }
+        (self.decode)(&bytes[8..])
+    }
+
+    /// Write value of `Type` to the cache.
+    pub fn store<Type: 'static>(&self, value: &T) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut bytes = self.version.to_le_bytes().to_vec();
+        bytes.extend((self.encode)(value));
+        // Write to temporary file first, so concurrent readers never observe
+        // partially written value.
+        let path = self.path::<Type>();
+        let tmp = path.with_extension(format!(
+            "tmp{}-{}",
+            std::process::id(),
+            TMP_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp, bytes)?;
+        fs::rename(tmp, path)
+    }
+
+    /// Read cached value of `Type`, or compute and store it.
+    ///
+    /// Computation is guarded by lock file, so when several processes, for
+    /// example prefork workers, share cache directory, value is computed by
+    /// one of them and loaded by others. Lock file is removed once value is
+    /// stored. If lock can not be taken, or is not released within 30
+    /// seconds, for example because its holder crashed, value is computed
+    /// without it.
+    pub fn load_or_init<Type, Init>(&self, f: Init) -> T
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        if let Some(value) = self.load::<Type>() {
+            return value;
+        }
+        let _lock = self.lock::<Type>();
+        // Other process might have stored value while we were waiting
+        if let Some(value) = self.load::<Type>() {
+            return value;
+        }
+        let value = f();
+        let _ = self.store::<Type>(&value);
+        value
+    }
+
+    /// Remove cached value of `Type`, and its lock file, if it was left by
+    /// crashed process.
+    pub fn invalidate<Type: 'static>(&self) -> io::Result<()> {
+        remove_file(&self.lock_path::<Type>())?;
+        remove_file(&self.path::<Type>())
+    }
+
+    /// Remove all cached values.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Take exclusive lock of `Type` file, released when returned guard is
+    /// dropped. Returns `None` without lock once value of `Type` is stored by
+    /// the holder.
+    fn lock<Type: 'static>(&self) -> Option<LockFile> {
+        fs::create_dir_all(&self.dir).ok()?;
+        let path = self.lock_path::<Type>();
+        let start = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Some(LockFile(path)),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(_) => return None,
+            }
+            if start.elapsed() >= LOCK_TIMEOUT || self.load::<Type>().is_some() {
+                return None;
+            }
+            thread::sleep(LOCK_POLL);
+        }
+    }
+
+    fn lock_path<Type: 'static>(&self) -> PathBuf {
+        self.path::<Type>().with_extension("lock")
+    }
+
+    fn path<Type: 'static>(&self) -> PathBuf {
+        let name = std::any::type_name::<Type>();
+        // Type names are not valid file names in general, so keep only safe
+        // characters and add hash of full name to keep names distinct.
+        let safe: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(64)
+            .collect();
+        self.dir.join(format!("{}-{:016x}.bin", safe, fnv1a(name)))
+    }
+}
+
+/// Remove file, if it exists.
+fn remove_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Same as [`call_once`](Self::call_once), but value is first looked up
+    /// in disk cache, and stored there after initialization, see
+    /// [`DiskCache::load_or_init`].
+    #[track_caller]
+    pub fn call_once_cached<Type, Init>(&'static self, cache: &DiskCache<T>, f: Init) -> &'static T
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        self.call_once::<Type, _>(|| cache.load_or_init::<Type, _>(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    fn cache(version: u64) -> DiskCache<String> {
+        let dir =
+            std::env::temp_dir().join(format!("generic_static_persist_{}", std::process::id()));
+        DiskCache::new(
+            dir,
+            version,
+            |value| value.as_bytes().to_vec(),
+            |bytes| String::from_utf8(bytes.to_vec()).ok(),
+        )
+    }
+
+    #[test]
+    fn cached_values_survive_and_versions_invalidate() {
+        static FIRST: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        static SECOND: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        let v1 = cache(1);
+        v1.clear().unwrap();
+
+        FIRST.call_once_cached::<Vec<u8>, _>(&v1, || "computed".to_string());
+        let restored = SECOND.call_once_cached::<Vec<u8>, _>(&v1, || unreachable!());
+        assert_eq!(restored, "computed");
+
+        assert_eq!(cache(2).load::<Vec<u8>>(), None);
+        v1.clear().unwrap();
+    }
+
+    #[test]
+    fn concurrent_stores_do_not_share_temporary_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "generic_static_persist_concurrent_{}",
+            std::process::id()
+        ));
+        let caches: Vec<_> = (0..8)
+            .map(|_| {
+                DiskCache::new(
+                    dir.clone(),
+                    1,
+                    |value: &String| value.as_bytes().to_vec(),
+                    |bytes| String::from_utf8(bytes.to_vec()).ok(),
+                )
+            })
+            .collect();
+
+        std::thread::scope(|scope| {
+            for cache in &caches {
+                scope.spawn(move || {
+                    for _ in 0..50 {
+                        cache.store::<u8>(&"stored".to_string()).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(caches[0].load::<u8>().as_deref(), Some("stored"));
+        caches[0].clear().unwrap();
+    }
+
+    #[test]
+    fn lock_files_are_removed() {
+        let dir =
+            std::env::temp_dir().join(format!("generic_static_persist_lock_{}", std::process::id()));
+        let cache = DiskCache::new(
+            dir,
+            1,
+            |value: &String| value.as_bytes().to_vec(),
+            |bytes| String::from_utf8(bytes.to_vec()).ok(),
+        );
+        let lock = cache.lock_path::<u8>();
+
+        assert_eq!(cache.load_or_init::<u8, _>(|| "computed".to_string()), "computed");
+        assert!(!lock.exists());
+
+        // Left by crashed process
+        fs::write(&lock, b"").unwrap();
+        cache.invalidate::<u8>().unwrap();
+        assert!(!lock.exists());
+        assert_eq!(cache.load::<u8>(), None);
+        cache.clear().unwrap();
+    }
+}
@@ -0,0 +1,40 @@
+use crate::StaticTypeMap;
+
+/// Types capable of providing value stored for them in [`StaticTypeMap`].
+///
+/// Allows to keep initialization logic next to type definition, instead of
+/// repeating it at every call site.
+///
+/// ```
+/// use generic_static::{ProvideValue, StaticTypeMap};
+/// use once_cell::sync::OnceCell;
+///
+/// struct A;
+/// impl ProvideValue<String> for A {
+///     fn provide() -> String {
+///         "A".to_string()
+///     }
+/// }
+///
+/// fn map() -> &'static StaticTypeMap<String> {
+///     static VALUE: OnceCell<StaticTypeMap<String>> = OnceCell::new();
+///     VALUE.get_or_init(StaticTypeMap::new)
+/// }
+///
+/// assert_eq!(map().call_once_default::<A>(), "A");
+/// ```
+pub trait ProvideValue<T> {
+    fn provide() -> T;
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Initialize static value corresponding to provided type with
+    /// [`ProvideValue::provide`].
+    #[track_caller]
+    pub fn call_once_default<Type>(&'static self) -> &'static T
+    where
+        Type: ProvideValue<T> + 'static,
+    {
+        self.call_once::<Type, _>(Type::provide)
+    }
+}
@@ -0,0 +1,248 @@
+//! Low level access to storage of [`StaticTypeMap`].
+//!
+//! Each type key owns a leaked `OnceCell`, which lives until program
+//! terminates. Functions of this module hand out these cells directly, so
+//! they can be stored in custom fast path structures or combined with
+//! custom synchronization.
+//!
+//! Values initialized through raw cells bypass map bookkeeping: `on_init`
+//! hooks are not called, and initialization is not reflected in
+//! statistics.
+//!
+//! ## Hot paths
+//!
+//! Every `call_once` looks entry up in the map under read lock. Code which
+//! queries the same types in a loop can look entries up once, and keep
+//! returned [`RawEntry`] or cell: subsequent access is a single atomic load
+//! without any locking or hashing. Crate does not provide unchecked
+//! accessors, since it forbids `unsafe` code, but `OnceCell::get_unchecked`
+//! can be used on the cell directly.
+//!
+//! ```
+//! use generic_static::{raw, StaticTypeMap};
+//! use once_cell::sync::Lazy;
+//!
+//! static WEIGHTS: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+//!
+//! WEIGHTS.call_once::<u8, _>(|| 8);
+//! let weight = raw::entry::<u8, _, _>(&WEIGHTS);
+//! let total: u32 = (0..1000).map(|_| *weight.get().unwrap()).sum();
+//! assert_eq!(total, 8000);
+//! ```
+//!
+//! This module is part of stable public API and follows the same semver
+//! guarantees as the rest of the crate. `OnceCell` is
+//! `once_cell::sync::OnceCell` from `once_cell` 1.x.
+
+use std::any::TypeId;
+
+use once_cell::sync::OnceCell;
+
+use crate::{Entry, Global, StaticTypeMap};
+
+/// Get cell corresponding to provided type, creating it if needed.
+///
+/// Cell is not initialized by this call.
+///
+/// ```
+/// use generic_static::{raw, StaticTypeMap};
+/// use once_cell::sync::Lazy;
+///
+/// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+///
+/// let cell = raw::cell::<u8, _, _>(&MAP);
+/// assert_eq!(cell.get(), None);
+/// cell.set(8).unwrap();
+/// assert_eq!(MAP.call_once::<u8, _>(|| unreachable!()), &8);
+/// ```
+pub fn cell<Type, T, NS>(map: &'static StaticTypeMap<T, NS>) -> &'static OnceCell<T>
+where
+    Type: 'static,
+    T: 'static,
+    NS: 'static,
+{
+    map.cell_of::<Type>()
+}
+
+/// Get cell corresponding to type with provided `TypeId`, if it exists.
+pub fn get_cell<T, NS>(map: &StaticTypeMap<T, NS>, id: TypeId) -> Option<&'static OnceCell<T>>
+where
+    T: 'static,
+    NS: 'static,
+{
+    map.owned_entry(id).map(|entry| &entry.cell)
+}
+
+/// Handle to entry of single key type.
+///
+/// Gives access to entry metadata and its cell, and allows to publish
+/// values created outside of the map, while keeping map bookkeeping:
+/// published values are counted in statistics and passed to `on_init`
+/// hooks.
+pub struct RawEntry<T: 'static, NS: 'static = Global> {
+    map: &'static StaticTypeMap<T, NS>,
+    entry: &'static Entry<T>,
+}
+
+impl<T: 'static, NS: 'static> RawEntry<T, NS> {
+    /// `TypeId` of key type.
+    pub fn type_id(&self) -> TypeId {
+        self.entry.type_id
+    }
+
+    /// Name of key type, or `"<unknown>"` if entry was only accessed by
+    /// `TypeId` so far.
+    pub fn type_name(&self) -> &'static str {
+        self.entry.type_name()
+    }
+
+    /// Cell holding value of the entry. Address of the cell never changes.
+    pub fn cell(&self) -> &'static OnceCell<T> {
+        &self.entry.cell
+    }
+
+    /// Initialized value of the entry.
+    pub fn get(&self) -> Option<&'static T> {
+        self.entry.cell.get()
+    }
+
+    /// Initialize entry with provided value, or return the value back if
+    /// entry is already initialized.
+    pub fn publish(&self, value: T) -> Result<&'static T, T> {
+        let mut value = Some(value);
+        match self
+            .map
+            .get_or_init_tracked(self.entry, || value.take().unwrap())
+        {
+            (published, true) => Ok(published),
+            _ => Err(value.take().unwrap()),
+        }
+    }
+}
+
+impl<T: 'static, NS: 'static> Clone for RawEntry<T, NS> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, NS: 'static> Copy for RawEntry<T, NS> {}
+
+/// Get entry corresponding to provided type, creating it if needed.
+///
+/// For child maps without value of their own, entry of the parent is
+/// returned, see [`StaticTypeMap::child_of`].
+///
+/// ```
+/// use generic_static::{raw, StaticTypeMap};
+/// use once_cell::sync::Lazy;
+///
+/// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+///
+/// let entry = raw::entry::<u8, _, _>(&MAP);
+/// assert_eq!(entry.type_name(), "u8");
+/// assert_eq!(entry.publish(8), Ok(&8));
+/// assert_eq!(entry.publish(9), Err(9));
+/// assert_eq!(MAP.try_initialized::<u8>(), Ok(&8));
+/// ```
+pub fn entry<Type, T, NS>(map: &'static StaticTypeMap<T, NS>) -> RawEntry<T, NS>
+where
+    Type: 'static,
+    T: 'static,
+    NS: 'static,
+{
+    let map = map.owner(TypeId::of::<Type>());
+    RawEntry {
+        map,
+        entry: map.entry::<Type>(),
+    }
+}
+
+/// Get entry corresponding to type with provided `TypeId`, creating it if
+/// needed. `type_name` is used in diagnostics.
+pub fn entry_by_id<T, NS>(
+    map: &'static StaticTypeMap<T, NS>,
+    id: TypeId,
+    type_name: &'static str,
+) -> RawEntry<T, NS>
+where
+    T: 'static,
+    NS: 'static,
+{
+    let map = map.owner(id);
+    RawEntry {
+        map,
+        entry: map.entry_by_id(id, type_name),
+    }
+}
+
+/// All entries of the map, initialized or not.
+pub fn entries<T, NS>(map: &'static StaticTypeMap<T, NS>) -> Vec<RawEntry<T, NS>>
+where
+    T: 'static,
+    NS: 'static,
+{
+    map.entries()
+        .into_iter()
+        .map(|entry| RawEntry { map, entry })
+        .collect()
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Get cell corresponding to provided type, creating it if needed.
+    ///
+    /// Cell is not initialized by this call, so it can be passed to code
+    /// working with `OnceCell` directly, for example to initialize it with
+    /// `get_or_try_init`. See [`raw`](crate::raw) module for caveats.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let cell = MAP.cell_of::<u8>();
+    /// assert!(cell.get_or_try_init(|| "8".parse::<u32>()).is_ok());
+    /// assert_eq!(cell.get(), Some(&8));
+    /// ```
+    pub fn cell_of<Type>(&'static self) -> &'static OnceCell<T>
+    where
+        Type: 'static,
+    {
+        let map = self.owner(TypeId::of::<Type>());
+        &map.entry::<Type>().cell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn entries_created_by_id_are_shared_with_typed_access() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        let entry = entry_by_id(&MAP, TypeId::of::<u16>(), "u16");
+        entry.publish(16).unwrap();
+
+        assert_eq!(*MAP.call_once::<u16, _>(|| unreachable!()), 16);
+        assert!(std::ptr::eq(entry.cell(), cell::<u16, _, _>(&MAP)));
+        let all = entries(&MAP);
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].type_id(), TypeId::of::<u16>());
+    }
+
+    #[test]
+    fn namespaced_maps_are_supported() {
+        use crate::DynTypeMap;
+
+        struct Parser;
+        static MAP: Lazy<StaticTypeMap<u32, Parser>> = Lazy::new(StaticTypeMap::new);
+        cell::<u8, _, _>(&MAP).set(8).unwrap();
+
+        assert_eq!(get_cell(&MAP, TypeId::of::<u8>()).unwrap().get(), Some(&8));
+        let _guard = crate::testing::override_for::<u8, _, _>(&MAP, 9);
+        let dynamic = crate::DynStaticMap::new(&MAP);
+        assert_eq!(dynamic.get_or_init_dyn(TypeId::of::<u8>(), &|| 0), &9);
+    }
+}
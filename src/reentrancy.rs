@@ -0,0 +1,49 @@
+use std::cell::RefCell;
+
+thread_local! {
+    /// Addresses and type names of entries, which are being initialized on
+    /// current thread, innermost last.
+    static INITIALIZING: RefCell<Vec<(usize, &'static str)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks entry as being initialized on current thread, until dropped.
+pub(crate) struct InitGuard {
+    entry: usize,
+}
+
+impl InitGuard {
+    pub(crate) fn enter<E>(entry: &E, type_name: &'static str) -> Self {
+        let entry = entry as *const E as usize;
+        INITIALIZING.with(|stack| stack.borrow_mut().push((entry, type_name)));
+        Self { entry }
+    }
+}
+
+impl Drop for InitGuard {
+    fn drop(&mut self) {
+        INITIALIZING.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(position) = stack.iter().rposition(|(entry, _)| *entry == self.entry) {
+                stack.remove(position);
+            }
+        });
+    }
+}
+
+/// Type name of innermost entry being initialized on current thread.
+pub(crate) fn current() -> Option<&'static str> {
+    INITIALIZING.with(|stack| stack.borrow().last().map(|(_, name)| *name))
+}
+
+/// If entry is being initialized on current thread, names of types from the
+/// entry to the innermost one, followed by the entry again.
+pub(crate) fn cycle<E>(entry: &E) -> Option<Vec<&'static str>> {
+    let entry = entry as *const E as usize;
+    INITIALIZING.with(|stack| {
+        let stack = stack.borrow();
+        let start = stack.iter().position(|(e, _)| *e == entry)?;
+        let mut names: Vec<_> = stack[start..].iter().map(|(_, name)| *name).collect();
+        names.push(stack[start].1);
+        Some(names)
+    })
+}
@@ -0,0 +1,108 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Map from key type to `'static` reference owned by the caller.
+///
+/// Unlike [`StaticTypeMap`](crate::StaticTypeMap), map neither allocates nor
+/// copies values: references to true statics, `lazy_static` values or
+/// `Box::leak`ed values are stored as is. Values may be unsized, which
+/// allows to register per-type trait objects.
+///
+/// ```
+/// use generic_static::StaticTypeRefMap;
+/// use std::fmt::Debug;
+///
+/// static ZERO: u32 = 0;
+///
+/// let defaults = StaticTypeRefMap::<dyn Debug + Sync>::new();
+/// defaults.register::<u32>(&ZERO).unwrap();
+/// defaults.register::<String>(Box::leak(Box::new(String::new()))).unwrap();
+///
+/// assert_eq!(format!("{:?}", defaults.get::<u32>().unwrap()), "0");
+/// assert!(defaults.get::<u8>().is_none());
+/// ```
+pub struct StaticTypeRefMap<T: ?Sized + 'static> {
+    map: RwLock<HashMap<TypeId, &'static T>>,
+}
+
+impl<T: ?Sized + 'static> StaticTypeRefMap<T> {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Associate reference with provided type.
+    ///
+    /// Fails with already registered reference, if there is one.
+    pub fn register<Type>(&self, value: &'static T) -> Result<(), &'static T>
+    where
+        Type: 'static,
+    {
+        let mut writer = self.map.write().unwrap();
+        match writer.get(&TypeId::of::<Type>()) {
+            Some(existing) => Err(*existing),
+            None => {
+                writer.insert(TypeId::of::<Type>(), value);
+                Ok(())
+            }
+        }
+    }
+
+    /// Get reference associated with provided type, registering one
+    /// returned by `f` if there is none.
+    ///
+    /// `f` is called while holding write lock of the map, so it must not
+    /// access the map.
+    pub fn get_or_register<Type, Init>(&self, f: Init) -> &'static T
+    where
+        Type: 'static,
+        Init: FnOnce() -> &'static T,
+    {
+        if let Some(value) = self.get::<Type>() {
+            return value;
+        }
+        let mut writer = self.map.write().unwrap();
+        let value: &'static T = writer.entry(TypeId::of::<Type>()).or_insert_with(f);
+        value
+    }
+
+    /// Get reference associated with provided type.
+    pub fn get<Type>(&self) -> Option<&'static T>
+    where
+        Type: 'static,
+    {
+        self.map.read().unwrap().get(&TypeId::of::<Type>()).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: ?Sized + 'static> Default for StaticTypeRefMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_are_stored_as_is() {
+        static NAME: &str = "u8";
+        let map = StaticTypeRefMap::<str>::new();
+
+        assert!(std::ptr::eq(map.get_or_register::<u8, _>(|| NAME), NAME));
+        assert!(map.register::<u8>("other").is_err());
+        assert!(std::ptr::eq(map.get::<u8>().unwrap(), NAME));
+        assert_eq!(map.len(), 1);
+    }
+}
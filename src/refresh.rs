@@ -0,0 +1,108 @@
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::ArcTypeMap;
+
+/// Background refresh of single value, see
+/// [`ArcTypeMap::refresh_every`]. Refreshing stops when dropped.
+pub struct Refresher {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for Refresher {
+    /// Stop refreshing and wait until refresh in progress, if any, is
+    /// published.
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            // Refresher dropped by the refresh itself can not wait for it
+            if thread.thread().id() != thread::current().id() {
+                let _ = thread.join();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Refresher {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Refresher").finish_non_exhaustive()
+    }
+}
+
+impl<T> ArcTypeMap<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Recompute value corresponding to provided type every `interval` on
+    /// background thread, publishing each result with
+    /// [`replace`](Self::replace). Dropping returned [`Refresher`] stops
+    /// refreshing and waits for the background thread to exit.
+    ///
+    /// ```
+    /// use generic_static::ArcTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use std::sync::mpsc::channel;
+    /// use std::time::Duration;
+    ///
+    /// static MAP: Lazy<ArcTypeMap<u32>> = Lazy::new(ArcTypeMap::new);
+    /// static VERSION: AtomicU32 = AtomicU32::new(0);
+    ///
+    /// let next = || VERSION.fetch_add(1, Ordering::SeqCst);
+    /// assert_eq!(*MAP.call_once::<u8, _>(next), 0);
+    ///
+    /// let (refreshed, refreshes) = channel();
+    /// let refresher = MAP.refresh_every::<u8, _>(Duration::from_millis(10), move || {
+    ///     let version = next();
+    ///     let _ = refreshed.send(version);
+    ///     version
+    /// });
+    /// assert!(refreshes.recv().unwrap() > 0);
+    /// drop(refresher);
+    /// assert!(*MAP.call_once::<u8, _>(next) > 0);
+    /// ```
+    pub fn refresh_every<Type, Refresh>(&'static self, interval: Duration, f: Refresh) -> Refresher
+    where
+        Type: 'static,
+        Refresh: Fn() -> T + Send + 'static,
+    {
+        let (stop, stopped) = channel::<()>();
+        let thread = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                self.replace::<Type>(f());
+            }
+        });
+        Refresher {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::sync::mpsc::TryRecvError;
+
+    #[test]
+    fn dropped_refresher_stops() {
+        static MAP: Lazy<ArcTypeMap<u32>> = Lazy::new(ArcTypeMap::new);
+
+        let (refreshed, refreshes) = channel();
+        let refresher = MAP.refresh_every::<u8, _>(Duration::from_millis(5), move || {
+            let _ = refreshed.send(());
+            1
+        });
+        refreshes.recv().unwrap();
+        drop(refresher);
+        assert!(MAP.get::<u8>().is_some());
+
+        // Refresh closure, and with it the sender, is dropped once thread
+        // exits, which has already happened when `drop` returned
+        while refreshes.try_recv().is_ok() {}
+        assert_eq!(refreshes.try_recv(), Err(TryRecvError::Disconnected));
+    }
+}
@@ -0,0 +1,156 @@
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Map of per type values, which must be explicitly registered before use.
+///
+/// Unlike [`StaticTypeMap`](crate::StaticTypeMap) nothing is initialized
+/// lazily: looking up type which was never registered is an error.
+///
+/// ```
+/// use generic_static::TypedRegistry;
+///
+/// let mut registry = TypedRegistry::new();
+/// registry.register::<u32>("u32 handler");
+///
+/// assert_eq!(registry.get::<u32>(), Ok(&"u32 handler"));
+/// assert!(registry.get::<u64>().is_err());
+/// ```
+pub struct TypedRegistry<T> {
+    map: HashMap<TypeId, T>,
+}
+
+impl<T> TypedRegistry<T> {
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+
+    /// Register value corresponding to provided type.
+    ///
+    /// Returns previously registered value, if any.
+    pub fn register<Type>(&mut self, value: T) -> Option<T>
+    where
+        Type: 'static,
+    {
+        self.map.insert(TypeId::of::<Type>(), value)
+    }
+
+    /// Get value registered for provided type.
+    pub fn get<Type>(&self) -> Result<&T, NotRegistered>
+    where
+        Type: 'static,
+    {
+        self.map
+            .get(&TypeId::of::<Type>())
+            .ok_or_else(NotRegistered::of::<Type>)
+    }
+
+    pub fn is_registered<Type>(&self) -> bool
+    where
+        Type: 'static,
+    {
+        self.map.contains_key(&TypeId::of::<Type>())
+    }
+
+    /// Iterate over all registered values mutably.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.map.values_mut()
+    }
+
+    /// Remove all values, for which predicate returns `false`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(TypeId, &T) -> bool,
+    {
+        self.map.retain(|id, value| f(*id, value));
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<T> Default for TypedRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error returned when looking up type which was never registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotRegistered {
+    type_name: &'static str,
+}
+
+impl NotRegistered {
+    fn of<Type>() -> Self {
+        Self {
+            type_name: type_name::<Type>(),
+        }
+    }
+
+    /// Name of type, which was looked up.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Display for NotRegistered {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "type `{}` was never registered", self.type_name)
+    }
+}
+
+impl Error for NotRegistered {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_type_is_reported_by_name() {
+        let mut registry = TypedRegistry::new();
+        assert_eq!(registry.register::<u8>(1), None);
+        assert_eq!(registry.register::<u8>(2), Some(1));
+
+        let err = registry.get::<Vec<u16>>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "type `alloc::vec::Vec<u16>` was never registered"
+        );
+        assert_eq!(registry.get::<u8>(), Ok(&2));
+    }
+
+    #[test]
+    fn values_mut_updates_all_values() {
+        let mut registry = TypedRegistry::new();
+        registry.register::<u8>(1);
+        registry.register::<u16>(2);
+
+        for value in registry.values_mut() {
+            *value *= 10;
+        }
+
+        assert_eq!(registry.get::<u8>(), Ok(&10));
+        assert_eq!(registry.get::<u16>(), Ok(&20));
+    }
+
+    #[test]
+    fn retain_removes_rejected_types() {
+        let mut registry = TypedRegistry::new();
+        registry.register::<u8>(1);
+        registry.register::<u16>(2);
+
+        registry.retain(|id, _| id == TypeId::of::<u16>());
+        assert!(!registry.is_registered::<u8>());
+        assert_eq!(registry.get::<u16>(), Ok(&2));
+        assert_eq!(registry.len(), 1);
+    }
+}
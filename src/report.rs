@@ -0,0 +1,168 @@
+use std::fmt::Write;
+use std::mem::size_of;
+#[cfg(feature = "stats")]
+use std::sync::atomic::Ordering;
+
+#[cfg(feature = "heap-size")]
+use crate::HeapSize;
+use crate::StaticTypeMap;
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Human readable table of map entries, suitable for dumping to logs.
+    ///
+    /// Lists every key type with size of value, time spent in initializer,
+    /// and with `access-time` feature, time since last access.
+    /// Uninitialized entries are marked with `-`. With `stats` feature,
+    /// first line includes hits and misses of the map, and every entry its
+    /// number of reads.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u64>> = Lazy::new(|| StaticTypeMap::named("example"));
+    ///
+    /// MAP.call_once::<u8, _>(|| 1);
+    /// MAP.call_once::<u8, _>(|| 1);
+    /// println!("{}", MAP.report());
+    /// // map `example`: 1 entries, 1 hits, 1 misses
+    /// // type  size  init   reads
+    /// // u8    8     541ns  2
+    /// ```
+    pub fn report(&self) -> String {
+        self.render_report(&|_| size_of::<T>())
+    }
+
+    /// Same as [`report`](Self::report), but size of every initialized
+    /// value includes heap memory owned by it.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(|| StaticTypeMap::named("example"));
+    ///
+    /// MAP.call_once::<u8, _>(|| String::with_capacity(100));
+    /// println!("{}", MAP.report_deep());
+    /// // map `example`: 1 entries, 0 hits, 1 misses
+    /// // type  size  init   reads
+    /// // u8    124   541ns  1
+    /// ```
+    #[cfg(feature = "heap-size")]
+    pub fn report_deep(&self) -> String
+    where
+        T: HeapSize,
+    {
+        self.render_report(&|value| size_of::<T>() + value.map_or(0, HeapSize::heap_size))
+    }
+
+    /// Render report, with size of value of every entry provided by `size`.
+    fn render_report(&self, size: &dyn Fn(Option<&T>) -> usize) -> String {
+        let mut entries = self.entries();
+        entries.sort_by_key(|entry| entry.type_name());
+
+        let mut rows = vec![header()];
+        for entry in &entries {
+            let init = match (entry.cell.get(), entry.init_duration.get()) {
+                (Some(_), Some(duration)) => format!("{:?}", duration),
+                (Some(_), None) => "external".to_string(),
+                (None, _) => "-".to_string(),
+            };
+            #[allow(unused_mut)]
+            let mut row = vec![
+                entry.type_name().to_string(),
+                size(entry.cell.get()).to_string(),
+                init,
+            ];
+            #[cfg(feature = "stats")]
+            row.push(entry.reads.load(Ordering::Relaxed).to_string());
+            #[cfg(feature = "access-time")]
+            row.push(match entry.last_access() {
+                Some(time) => format!("{:?} ago", time.elapsed()),
+                None => "-".to_string(),
+            });
+            rows.push(row);
+        }
+
+        let columns = rows[0].len();
+        let widths: Vec<_> = (0..columns)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut report = format!("map `{}`: {} entries", self.label(), entries.len());
+        #[cfg(feature = "stats")]
+        let _ = write!(report, ", {} hits, {} misses", self.hits(), self.misses());
+        report.push('\n');
+        for row in rows {
+            let mut line = String::new();
+            for (cell, width) in row.iter().zip(&widths) {
+                let _ = write!(line, "{:width$}  ", cell, width = width);
+            }
+            report.push_str(line.trim_end());
+            report.push('\n');
+        }
+        report
+    }
+}
+
+fn header() -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut header = vec!["type", "size", "init"];
+    #[cfg(feature = "stats")]
+    header.push("reads");
+    #[cfg(feature = "access-time")]
+    header.push("last access");
+    header.into_iter().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn report_lists_every_entry() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::named("report"));
+        MAP.call_once::<u16, _>(|| 16);
+        MAP.call_once::<u16, _>(|| 16);
+        MAP.cell_of::<u8>();
+
+        let report = MAP.report();
+        let lines: Vec<_> = report.lines().collect();
+        #[cfg(feature = "stats")]
+        assert_eq!(lines[0], "map `report`: 2 entries, 1 hits, 1 misses");
+        #[cfg(not(feature = "stats"))]
+        assert_eq!(lines[0], "map `report`: 2 entries");
+        assert!(lines[1].starts_with("type  size  init"));
+        let u16_row: Vec<_> = lines[2].split_whitespace().collect();
+        assert_eq!(u16_row[..2], ["u16", "4"]);
+        #[cfg(feature = "stats")]
+        assert_eq!(u16_row[3], "2");
+        let u8_row: Vec<_> = lines[3].split_whitespace().collect();
+        assert_eq!(u8_row[..3], ["u8", "4", "-"]);
+        #[cfg(feature = "stats")]
+        assert_eq!(u8_row[3], "0");
+    }
+
+    #[test]
+    #[cfg(feature = "heap-size")]
+    fn deep_report_includes_owned_memory() {
+        static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u16, _>(|| String::with_capacity(100));
+        MAP.cell_of::<u8>();
+
+        let report = MAP.report_deep();
+        let lines: Vec<_> = report.lines().collect();
+        let inline = size_of::<String>().to_string();
+        let deep = (size_of::<String>() + 100).to_string();
+        let u16_row: Vec<_> = lines[2].split_whitespace().collect();
+        assert_eq!(u16_row[..2], ["u16", deep.as_str()]);
+        let u8_row: Vec<_> = lines[3].split_whitespace().collect();
+        assert_eq!(u8_row[..3], ["u8", inline.as_str(), "-"]);
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::atomic::Ordering;
+
+use crate::StaticTypeMap;
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Reject initialization of any type not initialized yet.
+    ///
+    /// Intended to be called after warmup, which touches all legitimate key
+    /// types: after the map is sealed, initialization of new type panics with
+    /// its name. Values initialized before sealing are still accessible.
+    ///
+    /// ```should_panic
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// MAP.seal();
+    /// assert_eq!(*MAP.call_once::<u8, _>(|| unreachable!()), 8);
+    /// MAP.call_once::<u16, _>(|| 16); // panics
+    /// ```
+    pub fn seal(&self) {
+        self.sealed.store(true, Ordering::Release);
+    }
+
+    /// Whether map was sealed with [`seal`](Self::seal).
+    pub fn is_sealed(&self) -> bool {
+        self.sealed.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn sealed_map_rejects_new_types() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::named("sealed"));
+        MAP.call_once::<u8, _>(|| 8);
+        MAP.seal();
+
+        let err = catch_unwind(AssertUnwindSafe(|| MAP.call_once::<u16, _>(|| 16))).unwrap_err();
+        let message = err.downcast_ref::<String>().unwrap();
+        assert_eq!(message, "initialization of `u16` in sealed map `sealed`");
+        assert_eq!(*MAP.call_once::<u8, _>(|| unreachable!()), 8);
+    }
+
+    #[test]
+    fn rejected_types_are_not_allocated() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| 8);
+        MAP.seal();
+
+        for _ in 0..3 {
+            assert!(catch_unwind(|| MAP.call_once::<u16, _>(|| 16)).is_err());
+        }
+        assert_eq!(MAP.leaked_entries(), 1);
+        assert!(MAP.get_entry(std::any::TypeId::of::<u16>()).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{Global, StaticTypeMap};
+
+/// Map of static values, which are `Send` but not `Sync`.
+///
+/// Each value is protected by its own `Mutex`, so the map itself can be
+/// shared between threads. Access is provided through lock guards.
+/// Namespace `NS` has the same meaning as for [`StaticTypeMap`].
+///
+/// ```
+/// use generic_static::SendStaticTypeMap;
+/// use once_cell::sync::Lazy;
+/// use std::cell::Cell;
+///
+/// static MAP: Lazy<SendStaticTypeMap<Cell<u32>>> = Lazy::new(SendStaticTypeMap::new);
+///
+/// MAP.call_once::<u8, _>(|| Cell::new(1)).set(2);
+/// assert_eq!(MAP.call_once::<u8, _>(|| Cell::new(1)).get(), 2);
+/// ```
+pub struct SendStaticTypeMap<T: 'static, NS: 'static = Global> {
+    map: StaticTypeMap<Mutex<T>, NS>,
+}
+
+impl<T: Send + 'static, NS: 'static> SendStaticTypeMap<T, NS> {
+    pub fn new() -> Self {
+        Self {
+            map: StaticTypeMap::new(),
+        }
+    }
+
+    /// Initialize static value corresponding to provided type, and lock it.
+    ///
+    /// Same as [`StaticTypeMap::call_once`], but value stays locked until
+    /// returned guard is dropped.
+    pub fn call_once<Type, Init>(&'static self, f: Init) -> MutexGuard<'static, T>
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        self.map
+            .call_once::<Type, _>(|| Mutex::new(f()))
+            .lock()
+            .unwrap()
+    }
+}
+
+impl<T: Send + 'static, NS: 'static> Default for SendStaticTypeMap<T, NS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::cell::Cell;
+    use std::thread;
+
+    #[test]
+    fn values_are_shared_between_threads() {
+        static MAP: Lazy<SendStaticTypeMap<Cell<u32>>> = Lazy::new(SendStaticTypeMap::new);
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                thread::spawn(|| {
+                    let cell = MAP.call_once::<u8, _>(|| Cell::new(0));
+                    cell.set(cell.get() + 1);
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(MAP.call_once::<u8, _>(|| Cell::new(0)).get(), 4);
+    }
+}
@@ -0,0 +1,211 @@
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::{Entry, StaticTypeMap};
+
+/// Serialized as map from key type name to value. Only initialized
+/// values are included, ordered by type name, or by order of
+/// initialization with `insertion-order` feature.
+impl<T, NS: 'static> Serialize for StaticTypeMap<T, NS>
+where
+    T: Serialize + 'static,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[allow(unused_mut)]
+        let mut entries: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter_map(|entry| entry.cell.get().map(|value| (key_name(entry), value)))
+            .collect();
+        #[cfg(not(feature = "insertion-order"))]
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (name, value) in entries {
+            map.serialize_entry(&name, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Key of entry in serialized map. Entries, whose type is only known by
+/// `TypeId`, are keyed by its debug representation, so keys stay unique.
+fn key_name<T: 'static>(entry: &Entry<T>) -> Cow<'static, str> {
+    match entry.type_name.get() {
+        Some(name) => Cow::Borrowed(name),
+        None => Cow::Owned(format!("{:?}", entry.type_id)),
+    }
+}
+
+/// Key types expected in serialized snapshot, see [`StaticTypeMap::prewarm`].
+///
+/// Snapshot stores only type names, so types must be registered up front to
+/// map names back to `TypeId`.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotTypes {
+    types: HashMap<&'static str, TypeId>,
+}
+
+impl SnapshotTypes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `Type` as possible key of snapshot.
+    pub fn register<Type: 'static>(&mut self) -> &mut Self {
+        self.types
+            .insert(std::any::type_name::<Type>(), TypeId::of::<Type>());
+        self
+    }
+}
+
+impl<T, NS: 'static> StaticTypeMap<T, NS>
+where
+    T: 'static,
+{
+    /// Initialize values from snapshot, previously produced by `Serialize`.
+    ///
+    /// Values of unregistered types are skipped, as are values for types
+    /// already initialized. Returns number of values initialized from the
+    /// snapshot.
+    ///
+    /// ```
+    /// use generic_static::{SnapshotTypes, StaticTypeMap};
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let mut types = SnapshotTypes::new();
+    /// types.register::<u8>().register::<u16>();
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#"{"u8":8,"u16":16,"u64":64}"#);
+    /// assert_eq!(MAP.prewarm(&types, &mut de).unwrap(), 2);
+    /// assert_eq!(*MAP.call_once::<u8, _>(|| unreachable!()), 8);
+    /// ```
+    pub fn prewarm<'de, D>(
+        &'static self,
+        types: &SnapshotTypes,
+        deserializer: D,
+    ) -> Result<usize, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(PrewarmVisitor {
+            map: self,
+            types,
+            value: PhantomData,
+        })
+    }
+}
+
+struct PrewarmVisitor<'a, T: 'static, NS: 'static> {
+    map: &'static StaticTypeMap<T, NS>,
+    types: &'a SnapshotTypes,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'de, 'a, T, NS: 'static> Visitor<'de> for PrewarmVisitor<'a, T, NS>
+where
+    T: Deserialize<'de> + 'static,
+{
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map from type name to value")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<usize, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut count = 0;
+        while let Some(name) = access.next_key::<String>()? {
+            match self.types.types.get_key_value(name.as_str()) {
+                Some((type_name, id)) => {
+                    let value = access.next_value::<T>()?;
+                    let entry = self.map.entry_by_id(*id, type_name);
+                    if self.map.get_or_init_tracked(entry, || value).1 {
+                        count += 1;
+                    }
+                }
+                None => {
+                    access.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    #[cfg(not(feature = "insertion-order"))]
+    fn serialized_by_type_name() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u16, _>(|| 16);
+        MAP.call_once::<u8, _>(|| 8);
+
+        assert_eq!(
+            serde_json::to_string(&*MAP).unwrap(),
+            r#"{"u16":16,"u8":8}"#
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "insertion-order")]
+    fn serialized_in_insertion_order() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| 8);
+        MAP.call_once::<u32, _>(|| 32);
+        MAP.call_once::<u16, _>(|| 16);
+
+        assert_eq!(
+            serde_json::to_string(&*MAP).unwrap(),
+            r#"{"u8":8,"u32":32,"u16":16}"#
+        );
+    }
+
+    #[test]
+    fn unnamed_entries_have_unique_keys() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.get_or_init_dyn(TypeId::of::<u8>(), &|| 8);
+        MAP.get_or_init_dyn(TypeId::of::<u16>(), &|| 16);
+
+        let json: HashMap<String, u32> =
+            serde_json::from_str(&serde_json::to_string(&*MAP).unwrap()).unwrap();
+        assert_eq!(json[&format!("{:?}", TypeId::of::<u8>())], 8);
+        assert_eq!(json[&format!("{:?}", TypeId::of::<u16>())], 16);
+    }
+
+    #[test]
+    fn prewarm_restores_serialized_values() {
+        static SOURCE: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        static TARGET: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        SOURCE.call_once::<u8, _>(|| "eight".to_string());
+        SOURCE.call_once::<u16, _>(|| "sixteen".to_string());
+        TARGET.call_once::<u16, _>(|| "kept".to_string());
+
+        let json = serde_json::to_string(&*SOURCE).unwrap();
+        let mut types = SnapshotTypes::new();
+        types.register::<u8>().register::<u16>();
+        let mut de = serde_json::Deserializer::from_str(&json);
+
+        assert_eq!(TARGET.prewarm(&types, &mut de).unwrap(), 1);
+        assert_eq!(TARGET.call_once::<u8, _>(|| unreachable!()), "eight");
+        assert_eq!(TARGET.call_once::<u16, _>(|| unreachable!()), "kept");
+    }
+}
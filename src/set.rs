@@ -0,0 +1,64 @@
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+/// Set of types, which were marked as seen.
+///
+/// Lightweight alternative to `StaticTypeMap<()>` for "was this type
+/// processed before" checks, without storing any value per type.
+///
+/// ```
+/// use generic_static::StaticTypeSet;
+///
+/// let set = StaticTypeSet::new();
+/// assert!(set.insert::<u32>());
+/// assert!(!set.insert::<u32>());
+/// assert!(set.contains::<u32>());
+/// assert!(!set.contains::<u64>());
+/// ```
+pub struct StaticTypeSet {
+    set: RwLock<HashSet<TypeId>>,
+}
+
+impl StaticTypeSet {
+    pub fn new() -> Self {
+        Self {
+            set: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Mark provided type as seen.
+    ///
+    /// Returns `true` if type was not seen before.
+    pub fn insert<Type>(&self) -> bool
+    where
+        Type: 'static,
+    {
+        let id = TypeId::of::<Type>();
+        if self.set.read().unwrap().contains(&id) {
+            return false;
+        }
+        self.set.write().unwrap().insert(id)
+    }
+
+    pub fn contains<Type>(&self) -> bool
+    where
+        Type: 'static,
+    {
+        self.set.read().unwrap().contains(&TypeId::of::<Type>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for StaticTypeSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
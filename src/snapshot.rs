@@ -0,0 +1,50 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+
+use crate::StaticTypeMap;
+
+impl<T: Clone + 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Copy all initialized values into owned map.
+    ///
+    /// Snapshot is decoupled from the statics, so later initializations are
+    /// not reflected in it.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::any::TypeId;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// let snapshot = MAP.clone_snapshot();
+    /// MAP.call_once::<u16, _>(|| 16);
+    ///
+    /// assert_eq!(snapshot.len(), 1);
+    /// assert_eq!(snapshot[&TypeId::of::<u8>()], 8);
+    /// ```
+    pub fn clone_snapshot(&self) -> HashMap<TypeId, T> {
+        self.entries()
+            .into_iter()
+            .filter_map(|entry| entry.cell.get().map(|value| (entry.type_id, value.clone())))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::thread;
+
+    #[test]
+    fn snapshot_can_be_sent_across_threads() {
+        static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| "u8".to_string());
+        MAP.call_once::<u16, _>(|| "u16".to_string());
+
+        let snapshot = MAP.clone_snapshot();
+        let handle = thread::spawn(move || snapshot[&TypeId::of::<u16>()].clone());
+        assert_eq!(handle.join().unwrap(), "u16");
+    }
+}
@@ -0,0 +1,88 @@
+use std::any::TypeId;
+use std::future::Future;
+use std::panic::{resume_unwind, Location};
+
+use crate::StaticTypeMap;
+
+impl<T, NS: 'static> StaticTypeMap<T, NS>
+where
+    T: Send + Sync + 'static,
+{
+    /// Same as [`call_once`](Self::call_once), but runs initializer on
+    /// `tokio` blocking thread pool instead of blocking async worker.
+    ///
+    /// Concurrent callers of the same type also wait on blocking pool, so
+    /// long initializers do not stall executor. Already initialized values
+    /// are returned without spawning.
+    ///
+    /// # Panics
+    ///
+    /// Panics if initializer panics, or if called outside of tokio runtime.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let value = MAP.call_once_blocking::<u8, _>(|| "expensive".to_string()).await;
+    /// assert_eq!(value, "expensive");
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn call_once_blocking<Type, Init>(
+        &'static self,
+        f: Init,
+    ) -> impl Future<Output = &'static T>
+    where
+        Type: 'static,
+        Init: FnOnce() -> T + Send + 'static,
+    {
+        // Initializer runs on another thread, so call site is captured here
+        let location = Location::caller();
+        let map = self.owner(TypeId::of::<Type>());
+        let entry = map.entry::<Type>();
+        async move {
+            if entry.cell.get().is_some() {
+                return map.get_or_init_at(entry, location, f).0;
+            }
+            match tokio::task::spawn_blocking(move || map.get_or_init_at(entry, location, f)).await
+            {
+                Ok((value, _)) => value,
+                Err(err) => resume_unwind(err.into_panic()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn concurrent_callers_share_initialization() {
+        static MAP: Lazy<StaticTypeMap<usize>> = Lazy::new(StaticTypeMap::new);
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let init = || {
+            std::thread::sleep(Duration::from_millis(20));
+            CALLS.fetch_add(1, Ordering::SeqCst)
+        };
+
+        let (a, b) = tokio::join!(
+            MAP.call_once_blocking::<u8, _>(init),
+            MAP.call_once_blocking::<u8, _>(init),
+        );
+        assert!(std::ptr::eq(a, b));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        MAP.call_once_blocking::<u8, _>(init).await;
+        #[cfg(feature = "stats")]
+        assert_eq!(MAP.hottest_types(1), [("u8", 3)]);
+        assert_eq!(MAP.validate(), Ok(()));
+        assert_eq!(MAP.init_location::<u8>().unwrap().file(), file!());
+    }
+}
@@ -0,0 +1,185 @@
+use std::any::TypeId;
+use std::cmp::Reverse;
+#[cfg(feature = "stats")]
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use crate::StaticTypeMap;
+
+/// Initialization statistics of single entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryStats {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    /// Time spent in initializer.
+    pub init_duration: Duration,
+}
+
+/// Single initialization, see [`StaticTypeMap::init_order`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitRecord {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    /// Time, when initializer was started.
+    pub started: Instant,
+    /// Time spent in initializer.
+    pub init_duration: Duration,
+}
+
+/// Initialization statistics of map, see [`StaticTypeMap::stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub entries: Vec<EntryStats>,
+}
+
+impl Stats {
+    /// Total time spent in initializers.
+    pub fn total(&self) -> Duration {
+        self.entries.iter().map(|entry| entry.init_duration).sum()
+    }
+
+    /// Entry with the slowest initializer.
+    pub fn slowest(&self) -> Option<&EntryStats> {
+        self.entries.iter().max_by_key(|entry| entry.init_duration)
+    }
+
+    /// Average time spent in initializer.
+    pub fn mean(&self) -> Option<Duration> {
+        if self.entries.is_empty() {
+            None
+        } else {
+            Some(self.total() / self.entries.len() as u32)
+        }
+    }
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Collect initialization durations of all initialized entries.
+    ///
+    /// Entries are sorted from the slowest to the fastest.
+    pub fn stats(&self) -> Stats {
+        let mut entries: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter_map(|entry| {
+                entry.init_duration.get().map(|duration| EntryStats {
+                    type_id: entry.type_id,
+                    type_name: entry.type_name(),
+                    init_duration: *duration,
+                })
+            })
+            .collect();
+        entries.sort_by_key(|entry| Reverse(entry.init_duration));
+        Stats { entries }
+    }
+
+    /// List initialized entries in order their initializers were started.
+    pub fn init_order(&self) -> Vec<InitRecord> {
+        let mut records: Vec<_> = self
+            .entries()
+            .into_iter()
+            .filter_map(|entry| {
+                Some(InitRecord {
+                    type_id: entry.type_id,
+                    type_name: entry.type_name(),
+                    started: *entry.init_started.get()?,
+                    init_duration: *entry.init_duration.get()?,
+                })
+            })
+            .collect();
+        records.sort_by_key(|record| record.started);
+        records
+    }
+
+    /// Key types ranked by number of times their values were returned by
+    /// the map, most read first. At most `n` types are listed. Requires
+    /// `stats` feature.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// for _ in 0..3 {
+    ///     MAP.call_once::<u16, _>(|| 16);
+    /// }
+    /// assert_eq!(MAP.hottest_types(1), [("u16", 3)]);
+    /// ```
+    #[cfg(feature = "stats")]
+    pub fn hottest_types(&self, n: usize) -> Vec<(&'static str, u64)> {
+        let mut reads: Vec<_> = self
+            .entries()
+            .into_iter()
+            .map(|entry| (entry.type_name(), entry.reads.load(Ordering::Relaxed)))
+            .filter(|(_, reads)| *reads > 0)
+            .collect();
+        reads.sort_by_key(|(name, reads)| (Reverse(*reads), *name));
+        reads.truncate(n);
+        reads
+    }
+
+    /// Number of `call_once` calls, which returned already initialized
+    /// value. Requires `stats` feature.
+    #[cfg(feature = "stats")]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of `call_once` calls, which ran initializer. Requires `stats`
+    /// feature.
+    #[cfg(feature = "stats")]
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::thread::sleep;
+
+    #[test]
+    fn durations_are_recorded() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| 1);
+        MAP.call_once::<u16, _>(|| {
+            sleep(Duration::from_millis(20));
+            2
+        });
+
+        let stats = MAP.stats();
+        assert_eq!(stats.entries.len(), 2);
+        let slowest = stats.slowest().unwrap();
+        assert_eq!(slowest.type_id, TypeId::of::<u16>());
+        assert_eq!(slowest.type_name, "u16");
+        assert!(slowest.init_duration >= Duration::from_millis(20));
+        assert!(stats.total() >= slowest.init_duration);
+    }
+
+    #[test]
+    #[cfg(feature = "stats")]
+    fn hits_and_misses_are_counted() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        for _ in 0..3 {
+            MAP.call_once::<u8, _>(|| 1);
+        }
+        MAP.call_once::<u16, _>(|| 2);
+
+        assert_eq!(MAP.misses(), 2);
+        assert_eq!(MAP.hits(), 2);
+    }
+
+    #[test]
+    fn init_order_follows_start_time() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u32, _>(|| 1);
+        MAP.call_once::<u8, _>(|| MAP.call_once::<u16, _>(|| 2) + 1);
+        MAP.call_once::<u64, _>(|| 3);
+
+        let names: Vec<_> = MAP.init_order().iter().map(|r| r.type_name).collect();
+        assert_eq!(names, ["u32", "u8", "u16", "u64"]);
+    }
+}
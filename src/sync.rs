@@ -0,0 +1,24 @@
+//! Maps safe to share between threads, built on `std::sync` primitives.
+
+pub use crate::arc::ArcTypeMap;
+pub use crate::builder::StaticTypeMapBuilder;
+pub use crate::cached::cached_dyn;
+pub use crate::channels::StaticTypeChannels;
+pub use crate::counter::StaticTypeCounter;
+pub use crate::dispatch::DispatchTables;
+pub use crate::dynamic::{DynStaticMap, DynTypeMap};
+pub use crate::entry_ref::EntryRef;
+pub use crate::global::{global, singleton};
+pub use crate::index::StaticTypeIndex;
+pub use crate::inline::InlineTypeMap;
+pub use crate::interner::StaticTypeInterner;
+pub use crate::lazy::LazyTypeMap;
+pub use crate::multi::StaticTypeMultiMap;
+pub use crate::once::TypeOnce;
+pub use crate::ref_map::StaticTypeRefMap;
+pub use crate::refresh::Refresher;
+pub use crate::send::SendStaticTypeMap;
+pub use crate::set::StaticTypeSet;
+pub use crate::variants::OnceState;
+pub use crate::vec::{ItemsGuard, StaticTypeVec};
+pub use crate::StaticTypeMap;
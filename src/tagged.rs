@@ -0,0 +1,186 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, Visitor};
+use serde::ser::{self, SerializeMap, Serializer};
+
+type DeserializeFn<B> = Box<
+    dyn Fn(&mut dyn erased_serde::Deserializer) -> Result<B, erased_serde::Error> + Send + Sync,
+>;
+
+/// Registry of types, deserialized by tag into common representation `B`,
+/// such as `Box<dyn Trait>`.
+///
+/// Tagged values use externally tagged representation: map with single
+/// entry from tag to value.
+///
+/// ```
+/// use generic_static::TagRegistry;
+/// use serde::{Deserialize, Serialize};
+/// use std::any::TypeId;
+///
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Square(f64);
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// let mut registry = TagRegistry::<Box<dyn Shape>>::new();
+/// registry.register::<Square, _>("square", |square| Box::new(square));
+///
+/// let mut json = Vec::new();
+/// let value = Square(2.0);
+/// registry
+///     .serialize_tagged(TypeId::of::<Square>(), &value, &mut serde_json::Serializer::new(&mut json))
+///     .unwrap();
+/// assert_eq!(json, br#"{"square":2.0}"#);
+///
+/// let shape = registry
+///     .deserialize_tagged(&mut serde_json::Deserializer::from_slice(&json))
+///     .unwrap();
+/// assert_eq!(shape.area(), 4.0);
+/// ```
+pub struct TagRegistry<B> {
+    by_tag: HashMap<&'static str, DeserializeFn<B>>,
+    by_type: HashMap<TypeId, &'static str>,
+}
+
+impl<B> TagRegistry<B> {
+    pub fn new() -> Self {
+        Self {
+            by_tag: HashMap::new(),
+            by_type: HashMap::new(),
+        }
+    }
+
+    /// Register `Type` under provided tag, converted into `B` with `into`.
+    ///
+    /// Registering same tag or type again replaces previous registration.
+    pub fn register<Type, Into>(&mut self, tag: &'static str, into: Into)
+    where
+        Type: DeserializeOwned + 'static,
+        Into: Fn(Type) -> B + Send + Sync + 'static,
+    {
+        let deserialize: DeserializeFn<B> =
+            Box::new(move |de| erased_serde::deserialize::<Type>(de).map(&into));
+        self.by_tag.insert(tag, deserialize);
+        self.by_type.insert(TypeId::of::<Type>(), tag);
+    }
+
+    /// Tag registered for type with provided `TypeId`.
+    pub fn tag_of(&self, id: TypeId) -> Option<&'static str> {
+        self.by_type.get(&id).copied()
+    }
+
+    /// Deserialize value of type registered under provided tag.
+    pub fn deserialize<'de, D>(&self, tag: &str, deserializer: D) -> Result<B, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let deserialize = self
+            .by_tag
+            .get(tag)
+            .ok_or_else(|| de::Error::custom(format_args!("unknown tag `{}`", tag)))?;
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        deserialize(&mut erased).map_err(de::Error::custom)
+    }
+
+    /// Deserialize externally tagged value.
+    pub fn deserialize_tagged<'de, D>(&self, deserializer: D) -> Result<B, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(TaggedVisitor(self))
+    }
+
+    /// Serialize value as externally tagged, using tag registered for type
+    /// with provided `TypeId`.
+    pub fn serialize_tagged<S>(
+        &self,
+        id: TypeId,
+        value: &dyn erased_serde::Serialize,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let tag = self
+            .tag_of(id)
+            .ok_or_else(|| ser::Error::custom("type is not registered"))?;
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(tag, value)?;
+        map.end()
+    }
+}
+
+impl<B> Default for TagRegistry<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct TaggedVisitor<'a, B>(&'a TagRegistry<B>);
+
+impl<'de, 'a, B> Visitor<'de> for TaggedVisitor<'a, B> {
+    type Value = B;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map with single entry from tag to value")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<B, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let tag: String = access
+            .next_key()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        access.next_value_seed(TaggedSeed {
+            registry: self.0,
+            tag: &tag,
+        })
+    }
+}
+
+struct TaggedSeed<'a, B> {
+    registry: &'a TagRegistry<B>,
+    tag: &'a str,
+}
+
+impl<'de, 'a, B> DeserializeSeed<'de> for TaggedSeed<'a, B> {
+    type Value = B;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<B, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.registry.deserialize(self.tag, deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_tag_is_reported() {
+        let mut registry = TagRegistry::<u64>::new();
+        registry.register::<u8, _>("u8", u64::from);
+        registry.register::<u16, _>("u16", u64::from);
+
+        let mut de = serde_json::Deserializer::from_str(r#"{"u16":300}"#);
+        assert_eq!(registry.deserialize_tagged(&mut de).unwrap(), 300);
+        assert_eq!(registry.tag_of(TypeId::of::<u8>()), Some("u8"));
+
+        let mut de = serde_json::Deserializer::from_str(r#"{"u32":1}"#);
+        let err = registry.deserialize_tagged(&mut de).unwrap_err();
+        assert!(err.to_string().contains("unknown tag `u32`"));
+    }
+}
@@ -0,0 +1,251 @@
+//! Test support utilities, enabled by `testing` feature.
+//!
+//! Intended to be enabled only for tests, via `dev-dependencies`.
+
+use std::any::TypeId;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, ThreadId};
+
+use crate::{memory, Entry, StaticTypeMap};
+
+thread_local! {
+    static GENERATION: Cell<u64> = const { Cell::new(0) };
+}
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+pub(crate) fn current_generation() -> u64 {
+    GENERATION.with(Cell::get)
+}
+
+/// Code, which sees an override: threads sharing isolation generation, or
+/// only the thread which installed it, if that thread is not isolated.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OverrideScope {
+    generation: u64,
+    thread: Option<ThreadId>,
+}
+
+impl OverrideScope {
+    fn current() -> Self {
+        let generation = current_generation();
+        Self {
+            generation,
+            thread: if generation == 0 {
+                Some(thread::current().id())
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// Isolation generation, see [`isolate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Generation(u64);
+
+/// Make every map appear empty on current thread, until returned guard is
+/// dropped.
+///
+/// Entries are keyed by isolation generation of current thread, so code
+/// running under the guard neither sees values initialized outside of it
+/// nor leaks its own values to other tests running in the same process.
+/// Call it at the start of each test, which depends on fresh per-type
+/// state.
+///
+/// Generation is thread local: threads spawned by the test should call
+/// [`enter`] with [`IsolationGuard::generation`] to share its state.
+///
+/// ```
+/// use generic_static::{testing, StaticTypeMap};
+/// use once_cell::sync::Lazy;
+///
+/// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+///
+/// MAP.call_once::<u8, _>(|| 1);
+/// {
+///     let _isolation = testing::isolate();
+///     assert_eq!(*MAP.call_once::<u8, _>(|| 2), 2);
+/// }
+/// assert_eq!(*MAP.call_once::<u8, _>(|| 3), 1);
+/// ```
+pub fn isolate() -> IsolationGuard {
+    enter(Generation(NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)))
+}
+
+/// Enter existing isolation generation on current thread, until returned
+/// guard is dropped.
+pub fn enter(generation: Generation) -> IsolationGuard {
+    let previous = GENERATION.with(|current| current.replace(generation.0));
+    IsolationGuard {
+        generation,
+        previous,
+    }
+}
+
+/// Guard returned by [`isolate`] and [`enter`], restoring previous
+/// generation on drop.
+#[must_use = "isolation ends when guard is dropped"]
+pub struct IsolationGuard {
+    generation: Generation,
+    previous: u64,
+}
+
+impl IsolationGuard {
+    pub fn generation(&self) -> Generation {
+        self.generation
+    }
+}
+
+impl Drop for IsolationGuard {
+    fn drop(&mut self) {
+        GENERATION.with(|current| current.set(self.previous));
+    }
+}
+
+/// Override value corresponding to provided type, until returned guard is
+/// dropped.
+///
+/// While override is active, `call_once` and similar methods return `value`
+/// instead of stored value, without running initializer. Dropping the guard
+/// restores previous state, including previous override if guards are
+/// nested. Overrides do not affect cells obtained through
+/// [`raw`](crate::raw) module.
+///
+/// Override is only visible to code running in the same isolation
+/// generation as the caller, see [`isolate`]. Outside of isolation it is
+/// only visible to the current thread, so tests running in parallel do not
+/// observe overrides of each other.
+///
+/// Since references to value may outlive the guard, `value` is leaked.
+///
+/// ```
+/// use generic_static::{testing, StaticTypeMap};
+/// use once_cell::sync::Lazy;
+///
+/// static MAP: Lazy<StaticTypeMap<&'static str>> = Lazy::new(StaticTypeMap::new);
+///
+/// fn name<T: 'static>() -> &'static str {
+///     MAP.call_once::<T, _>(|| "real")
+/// }
+///
+/// {
+///     let _guard = testing::override_for::<u8, _, _>(&MAP, "fake");
+///     assert_eq!(name::<u8>(), "fake");
+/// }
+/// assert_eq!(name::<u8>(), "real");
+/// ```
+pub fn override_for<Type, T, NS>(map: &'static StaticTypeMap<T, NS>, value: T) -> OverrideGuard<T>
+where
+    Type: 'static,
+    T: 'static,
+    NS: 'static,
+{
+    let entry = map.entry::<Type>();
+    let value: &'static T = memory::intentional_leak_new(value);
+    entry
+        .overrides
+        .write()
+        .unwrap()
+        .push((OverrideScope::current(), value));
+    OverrideGuard { entry, value }
+}
+
+/// Guard returned by [`override_for`], restoring previous state on drop.
+#[must_use = "override is removed when guard is dropped"]
+pub struct OverrideGuard<T: 'static> {
+    entry: &'static Entry<T>,
+    value: &'static T,
+}
+
+impl<T: 'static> OverrideGuard<T> {
+    /// `TypeId` of overridden key type.
+    pub fn type_id(&self) -> TypeId {
+        self.entry.type_id
+    }
+}
+
+impl<T: 'static> Drop for OverrideGuard<T> {
+    fn drop(&mut self) {
+        let mut overrides = self.entry.overrides.write().unwrap();
+        if let Some(position) = overrides
+            .iter()
+            .rposition(|(_, value)| std::ptr::eq(*value, self.value))
+        {
+            overrides.remove(position);
+        }
+    }
+}
+
+impl<T: 'static> Entry<T> {
+    pub(crate) fn current_override(&self) -> Option<&'static T> {
+        let scope = OverrideScope::current();
+        self.overrides
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|(owner, _)| *owner == scope)
+            .map(|(_, value)| *value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn nested_overrides_are_restored() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        assert_eq!(*MAP.call_once::<u8, _>(|| 1), 1);
+
+        let outer = override_for::<u8, _, _>(&MAP, 2);
+        let inner = override_for::<u8, _, _>(&MAP, 3);
+        assert_eq!(*MAP.call_once::<u8, _>(|| 1), 3);
+        drop(outer);
+        assert_eq!(*MAP.call_once::<u8, _>(|| 1), 3);
+        drop(inner);
+        assert_eq!(*MAP.call_once::<u8, _>(|| 1), 1);
+    }
+
+    #[test]
+    fn overrides_are_not_visible_to_other_tests() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| 1);
+
+        let _guard = override_for::<u8, _, _>(&MAP, 2);
+        let other = std::thread::spawn(|| *MAP.call_once::<u8, _>(|| 3));
+        assert_eq!(other.join().unwrap(), 1);
+        assert_eq!(*MAP.call_once::<u8, _>(|| 3), 2);
+
+        let isolation = isolate();
+        let generation = isolation.generation();
+        let _guard = override_for::<u8, _, _>(&MAP, 4);
+        let entered = std::thread::spawn(move || {
+            let _isolation = enter(generation);
+            *MAP.call_once::<u8, _>(|| 5)
+        });
+        assert_eq!(entered.join().unwrap(), 4);
+    }
+
+    #[test]
+    fn isolated_generations_are_shared_with_spawned_threads() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.call_once::<u8, _>(|| 1);
+
+        let isolation = isolate();
+        let generation = isolation.generation();
+        let spawned = std::thread::spawn(move || {
+            let _isolation = enter(generation);
+            *MAP.call_once::<u8, _>(|| 2)
+        });
+
+        assert_eq!(spawned.join().unwrap(), 2);
+        assert_eq!(*MAP.call_once::<u8, _>(|| 3), 2);
+        assert_eq!(MAP.stats().entries.len(), 1);
+        drop(isolation);
+        assert_eq!(*MAP.call_once::<u8, _>(|| 3), 1);
+    }
+}
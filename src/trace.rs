@@ -0,0 +1,22 @@
+use std::time::Instant;
+
+/// Run initializer inside of `tracing` span, recording map, key type and
+/// initialization duration.
+pub(crate) fn initialize<T, Init>(map: &str, key: &str, f: Init) -> T
+where
+    Init: FnOnce() -> T,
+{
+    let span = tracing::info_span!(
+        "generic_static::init",
+        map,
+        key,
+        duration_us = tracing::field::Empty,
+    );
+    let _enter = span.enter();
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    span.record("duration_us", elapsed.as_micros() as u64);
+    tracing::debug!(map, key, ?elapsed, "initialized static value");
+    value
+}
@@ -0,0 +1,8 @@
+//! Maps without internal synchronization, populated through `&mut` and
+//! shared by reference once built.
+
+pub use crate::dense::DenseTypeMap;
+pub use crate::factory::FactoryRegistry;
+pub use crate::registry::TypedRegistry;
+#[cfg(feature = "erased-serde")]
+pub use crate::tagged::TagRegistry;
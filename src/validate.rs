@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::Ordering;
+
+use crate::StaticTypeMap;
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Check internal consistency of the map.
+    ///
+    /// Intended for tests and debug assertions. Checks are only reliable
+    /// while no other thread uses the map, since initialization in progress
+    /// is briefly seen as inconsistent state.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// assert!(MAP.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let mut problems = Vec::new();
+        let map = self.map.read().unwrap();
+
+        let mut orders = HashSet::new();
+        for (key, entry) in map.iter() {
+            if key.id != entry.type_id {
+                problems.push(format!(
+                    "entry of `{}` is stored under different TypeId",
+                    entry.type_name()
+                ));
+            }
+            if let Some(&order) = entry.order.get() {
+                if order >= self.initialized.load(Ordering::Relaxed) || !orders.insert(order) {
+                    problems.push(format!(
+                        "entry of `{}` has invalid initialization index {}",
+                        entry.type_name(),
+                        order
+                    ));
+                }
+            }
+            if entry.cell.get().is_some() {
+                if entry.poisoned.load(Ordering::Acquire) {
+                    problems.push(format!(
+                        "poisoned entry of `{}` has value",
+                        entry.type_name()
+                    ));
+                }
+            } else if entry.init_duration.get().is_some() {
+                problems.push(format!(
+                    "entry of `{}` finished initialization without value",
+                    entry.type_name()
+                ));
+            }
+        }
+        #[cfg(feature = "stats")]
+        self.check_counters(map.values().copied(), &mut problems);
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError {
+                map: self.label(),
+                problems,
+            })
+        }
+    }
+
+    /// Check that hits and misses agree with reads of entries.
+    #[cfg(feature = "stats")]
+    fn check_counters(
+        &self,
+        entries: impl Iterator<Item = &'static crate::Entry<T>>,
+        problems: &mut Vec<String>,
+    ) {
+        let mut reads = 0;
+        let mut initialized = 0;
+        for entry in entries {
+            reads += entry.reads.load(Ordering::Relaxed);
+            if entry.cell.get().is_some() {
+                initialized += 1;
+            }
+        }
+
+        let (hits, misses) = (self.hits(), self.misses());
+        if reads != hits + misses {
+            problems.push(format!(
+                "entries were read {} times, but map counted {} hits and {} misses",
+                reads, hits, misses
+            ));
+        }
+        if misses > initialized {
+            problems.push(format!(
+                "map counted {} misses, but only {} entries are initialized",
+                misses, initialized
+            ));
+        }
+    }
+}
+
+/// Inconsistencies found by [`StaticTypeMap::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    map: &'static str,
+    problems: Vec<String>,
+}
+
+impl ValidationError {
+    /// Description of every inconsistency found.
+    pub fn problems(&self) -> &[String] {
+        &self.problems
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "map `{}` is inconsistent: {}",
+            self.map,
+            self.problems.join("; ")
+        )
+    }
+}
+
+impl Error for ValidationError {}
+
+#[cfg(all(test, feature = "stats"))]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    #[test]
+    fn corrupted_counters_are_reported() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(|| StaticTypeMap::named("corrupted"));
+        MAP.call_once::<u8, _>(|| 8);
+        assert_eq!(MAP.validate(), Ok(()));
+
+        MAP.misses.fetch_add(1, Ordering::Relaxed);
+        let err = MAP.validate().unwrap_err();
+        assert_eq!(err.problems().len(), 2);
+        assert_eq!(
+            err.to_string(),
+            "map `corrupted` is inconsistent: entries were read 1 times, but map counted 0 hits \
+             and 2 misses; map counted 2 misses, but only 1 entries are initialized"
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use std::any::TypeId;
+use std::panic::Location;
+use std::pin::Pin;
+use std::sync::atomic::Ordering;
+
+use crate::{Error, StaticTypeMap};
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Same as [`StaticTypeMap::call_once`], but returns copy of stored
+    /// value.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let owned: String = MAP.call_once_cloned::<u8, _>(|| "u8".to_string());
+    /// std::thread::spawn(move || assert_eq!(owned, "u8")).join().unwrap();
+    /// ```
+    #[track_caller]
+    pub fn call_once_cloned<Type, Init>(&'static self, f: Init) -> T
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+        T: Clone,
+    {
+        self.call_once::<Type, _>(f).clone()
+    }
+
+    /// Same as [`StaticTypeMap::call_once`], but initializer may return any
+    /// value convertible into `T`.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// assert_eq!(MAP.call_once_into::<u8, _, _>(|| "u8"), "u8");
+    /// ```
+    #[track_caller]
+    pub fn call_once_into<Type, Init, V>(&'static self, f: Init) -> &'static T
+    where
+        Type: 'static,
+        Init: FnOnce() -> V,
+        V: Into<T>,
+    {
+        self.call_once::<Type, _>(|| f().into())
+    }
+
+    /// Same as [`StaticTypeMap::call_once`], but initializer is plain
+    /// function pointer, called with provided context.
+    ///
+    /// Unlike closures, every function pointer of the same signature has the
+    /// same type, so call sites do not produce separate instantiations.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// fn greet(name: &str) -> String {
+    ///     format!("Hello, {}!", name)
+    /// }
+    ///
+    /// assert_eq!(MAP.call_once_with::<u8, _>("u8", greet), "Hello, u8!");
+    /// ```
+    #[track_caller]
+    pub fn call_once_with<Type, C>(&'static self, ctx: C, f: fn(C) -> T) -> &'static T
+    where
+        Type: 'static,
+    {
+        let map = self.owner(TypeId::of::<Type>());
+        map.get_or_init(map.entry::<Type>(), move || f(ctx))
+    }
+
+    /// Initialize value with fallible initializer, storing value produced
+    /// by `fallback` if initializer fails.
+    ///
+    /// Error is returned along with stored value only by the call, which ran
+    /// failed initializer. Subsequent calls return stored fallback.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let (value, err) = MAP.call_once_or_else::<u8, _, _, _>(|| "x".parse(), |_| 0);
+    /// assert_eq!(*value, 0);
+    /// assert!(err.is_some());
+    ///
+    /// let (value, err) = MAP.call_once_or_else::<u8, _, _, _>(|| "1".parse(), |_| 0);
+    /// assert_eq!((*value, err), (0, None));
+    /// ```
+    #[track_caller]
+    pub fn call_once_or_else<Type, Init, Fallback, E>(
+        &'static self,
+        init: Init,
+        fallback: Fallback,
+    ) -> (&'static T, Option<E>)
+    where
+        Type: 'static,
+        Init: FnOnce() -> Result<T, E>,
+        Fallback: FnOnce(&E) -> T,
+    {
+        let mut error = None;
+        let value = self.call_once::<Type, _>(|| match init() {
+            Ok(value) => value,
+            Err(err) => {
+                let value = fallback(&err);
+                error = Some(err);
+                value
+            }
+        });
+        (value, error)
+    }
+
+    /// Set value returned by [`call_once_or_global`](Self::call_once_or_global)
+    /// for types without initialized value.
+    ///
+    /// Default can only be set once, otherwise provided value is returned
+    /// back.
+    pub fn set_global_default(&self, value: T) -> Result<(), T> {
+        self.global_default.set(value)
+    }
+
+    /// Get value corresponding to provided type if it is initialized,
+    /// otherwise map-wide default set with
+    /// [`set_global_default`](Self::set_global_default).
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static TIMEOUTS: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// assert_eq!(TIMEOUTS.call_once_or_global::<u8>(), None);
+    /// TIMEOUTS.set_global_default(30).unwrap();
+    /// TIMEOUTS.call_once::<u16, _>(|| 5);
+    ///
+    /// assert_eq!(TIMEOUTS.call_once_or_global::<u8>(), Some(&30));
+    /// assert_eq!(TIMEOUTS.call_once_or_global::<u16>(), Some(&5));
+    /// ```
+    pub fn call_once_or_global<Type>(&'static self) -> Option<&'static T>
+    where
+        Type: 'static,
+    {
+        let id = TypeId::of::<Type>();
+        self.owned_entry(id)
+            .and_then(|entry| entry.cell.get())
+            .or_else(|| self.global_default.get())
+    }
+
+    /// Same as [`call_once`](Self::call_once), but returns pinned
+    /// reference.
+    ///
+    /// Values are never moved or dropped by the map, so address of value
+    /// stays the same until program terminates, and value can rely on it,
+    /// for example to register itself in intrusive lists or pass its
+    /// address to FFI callbacks.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::pin::Pin;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let pinned: Pin<&'static u32> = MAP.call_once_pinned::<u8, _>(|| 8);
+    /// assert!(std::ptr::eq(pinned.get_ref(), MAP.call_once::<u8, _>(|| 9)));
+    /// ```
+    #[track_caller]
+    pub fn call_once_pinned<Type, Init>(&'static self, f: Init) -> Pin<&'static T>
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        Pin::static_ref(self.call_once::<Type, _>(f))
+    }
+
+    /// Same as [`call_once`](Self::call_once), but returns error instead of
+    /// panicking when value can not be initialized.
+    ///
+    /// Panics of initializer itself are propagated. If initializer of
+    /// another thread panics while this call waits for it, this call returns
+    /// [`Error::Poisoned`].
+    ///
+    /// ```
+    /// use generic_static::{Error, StaticTypeMap};
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// let mut cycle = None;
+    /// MAP.try_call_once::<u8, _>(|| {
+    ///     cycle = MAP.try_call_once::<u8, _>(|| 1).err();
+    ///     8
+    /// })
+    /// .unwrap();
+    /// assert_eq!(cycle, Some(Error::Cycle { type_names: vec!["u8", "u8"] }));
+    ///
+    /// MAP.seal();
+    /// assert_eq!(MAP.try_call_once::<u16, _>(|| 16), Err(Error::Sealed { type_name: "u16" }));
+    /// ```
+    #[track_caller]
+    pub fn try_call_once<Type, Init>(&'static self, f: Init) -> Result<&'static T, Error>
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        let map = self.owner(TypeId::of::<Type>());
+        let entry = map.try_entry_by_id(TypeId::of::<Type>(), std::any::type_name::<Type>())?;
+        if let Some(value) = map.lookup(entry) {
+            return Ok(value);
+        }
+        let mut f = Some(f);
+        map.try_init_slow(entry, Location::caller(), false, &mut || {
+            (f.take().unwrap())()
+        })
+        .map(|(value, _)| value)
+    }
+
+    /// Same as [`call_once`](Self::call_once), but runs initializer even if
+    /// previous initializer of the type panicked.
+    ///
+    /// Initializer receives [`OnceState`] telling whether previous attempt
+    /// panicked, which allows to recover from transient failures. Once value
+    /// is initialized, value is no longer poisoned for other callers.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::panic::catch_unwind;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// assert!(catch_unwind(|| MAP.call_once::<u8, _>(|| panic!("transient"))).is_err());
+    /// let value = MAP.call_once_force::<u8, _>(|state| if state.is_poisoned() { 1 } else { 0 });
+    /// assert_eq!(*value, 1);
+    /// assert_eq!(*MAP.call_once::<u8, _>(|| unreachable!()), 1);
+    /// ```
+    #[track_caller]
+    pub fn call_once_force<Type, Init>(&'static self, f: Init) -> &'static T
+    where
+        Type: 'static,
+        Init: FnOnce(&OnceState) -> T,
+    {
+        let map = self.owner(TypeId::of::<Type>());
+        let entry = map.entry::<Type>();
+        map.get_or_init_forced(entry, move || {
+            // Observed when initializer runs, poison is only cleared once it
+            // succeeds
+            let state = OnceState {
+                poisoned: entry.poisoned.load(Ordering::Acquire),
+            };
+            f(&state)
+        })
+    }
+}
+
+impl<V: ?Sized + 'static, NS: 'static> StaticTypeMap<&'static V, NS> {
+    /// Same as [`call_once`](Self::call_once), but for maps of references
+    /// returns stored reference itself.
+    ///
+    /// Only the reference is stored, so values which already live in
+    /// statics or were leaked before are not copied. See also
+    /// [`StaticTypeRefMap`](crate::StaticTypeRefMap), which does not
+    /// allocate entry per type.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static NAMES: Lazy<StaticTypeMap<&'static str>> = Lazy::new(StaticTypeMap::new);
+    /// static BYTE: &str = "byte";
+    ///
+    /// let name: &'static str = NAMES.call_once_static_ref::<u8, _>(|| BYTE);
+    /// assert!(std::ptr::eq(name, BYTE));
+    /// ```
+    #[track_caller]
+    pub fn call_once_static_ref<Type, Init>(&'static self, f: Init) -> &'static V
+    where
+        Type: 'static,
+        Init: FnOnce() -> &'static V,
+    {
+        self.call_once::<Type, _>(f)
+    }
+}
+
+/// State of initialization passed to [`StaticTypeMap::call_once_force`]
+/// initializer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnceState {
+    poisoned: bool,
+}
+
+impl OnceState {
+    /// Whether previous initializer of the type panicked.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn forced_initialization_after_success_is_noop() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        assert_eq!(
+            *MAP.call_once_force::<u8, _>(|state| state.is_poisoned() as u32),
+            0
+        );
+        assert_eq!(*MAP.call_once_force::<u8, _>(|_| unreachable!()), 0);
+
+        assert!(catch_unwind(|| MAP.call_once::<u16, _>(|| panic!("failed"))).is_err());
+        assert!(catch_unwind(|| MAP.call_once::<u16, _>(|| 1)).is_err());
+        assert_eq!(*MAP.call_once_force::<u16, _>(|_| 16), 16);
+    }
+
+    #[test]
+    fn entry_stays_poisoned_until_forced_initializer_succeeds() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        assert!(catch_unwind(|| MAP.call_once::<u8, _>(|| panic!("failed"))).is_err());
+
+        let forced = catch_unwind(|| {
+            MAP.call_once_force::<u8, _>(|_| {
+                // Concurrent callers still see poisoned entry
+                let other = std::thread::spawn(|| MAP.try_call_once::<u8, _>(|| 2));
+                assert_eq!(
+                    other.join().unwrap(),
+                    Err(Error::Poisoned { type_name: "u8" })
+                );
+                panic!("failed again")
+            })
+        });
+        assert!(forced.is_err());
+
+        let value = MAP.call_once_force::<u8, _>(|state| state.is_poisoned() as u32);
+        assert_eq!(*value, 1);
+        assert_eq!(*MAP.call_once::<u8, _>(|| unreachable!()), 1);
+    }
+
+    #[test]
+    fn waiters_of_panicking_initializer_get_error() {
+        use std::sync::mpsc::channel;
+
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        let (started, start) = channel();
+        let (go, wait) = channel::<()>();
+        let initializer = std::thread::spawn(move || {
+            MAP.call_once::<u8, _>(|| {
+                started.send(()).unwrap();
+                let _ = wait.recv();
+                panic!("failed")
+            });
+        });
+        start.recv().unwrap();
+        // Waits for initializer above, or finds it poisoned
+        let waiter = std::thread::spawn(|| MAP.try_call_once::<u8, _>(|| 8));
+        drop(go);
+        assert!(initializer.join().is_err());
+        assert_eq!(
+            waiter.join().unwrap(),
+            Err(Error::Poisoned { type_name: "u8" })
+        );
+    }
+}
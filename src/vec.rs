@@ -0,0 +1,179 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::{RwLock, RwLockReadGuard};
+
+use once_cell::sync::OnceCell;
+
+use crate::memory;
+
+struct List<T: 'static> {
+    items: RwLock<Vec<T>>,
+    sealed: OnceCell<&'static [T]>,
+}
+
+/// Map of append-only per type lists.
+///
+/// Items are pushed to list of key type, and can be read back through
+/// guard at any time. After list is sealed, no more items can be pushed,
+/// and items are available as `&'static [T]`.
+///
+/// ```
+/// use generic_static::StaticTypeVec;
+///
+/// struct Message;
+///
+/// let handlers = StaticTypeVec::new();
+/// handlers.push_for::<Message>("log").unwrap();
+/// handlers.push_for::<Message>("audit").unwrap();
+/// assert_eq!(handlers.read_for::<Message>().len(), 2);
+///
+/// let sealed: &'static [&str] = handlers.seal_for::<Message>();
+/// assert_eq!(sealed, ["log", "audit"]);
+/// assert_eq!(handlers.push_for::<Message>("late"), Err("late"));
+/// ```
+pub struct StaticTypeVec<T: 'static> {
+    map: RwLock<HashMap<TypeId, &'static List<T>>>,
+}
+
+impl<T: 'static> StaticTypeVec<T> {
+    pub fn new() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Append item to list of provided type.
+    ///
+    /// If list is already sealed, item is returned back as error.
+    pub fn push_for<Type>(&self, item: T) -> Result<(), T>
+    where
+        Type: 'static,
+    {
+        let list = self.list::<Type>();
+        let mut items = list.items.write().unwrap();
+        if list.sealed.get().is_some() {
+            return Err(item);
+        }
+        items.push(item);
+        Ok(())
+    }
+
+    /// Seal list of provided type, preventing further pushes.
+    ///
+    /// Sealed items will stay on heap until program terminated.
+    pub fn seal_for<Type>(&self) -> &'static [T]
+    where
+        Type: 'static,
+    {
+        let list = self.list::<Type>();
+        let mut items = list.items.write().unwrap();
+        list.sealed.get_or_init(|| {
+            let items = std::mem::take(&mut *items);
+            memory::intentional_leak_slice(items)
+        })
+    }
+
+    /// Items of provided type, if list was sealed.
+    pub fn get_for<Type>(&self) -> Option<&'static [T]>
+    where
+        Type: 'static,
+    {
+        self.get_list(TypeId::of::<Type>())
+            .and_then(|list| list.sealed.get().cloned())
+    }
+
+    /// Read current items of provided type.
+    ///
+    /// Pushes to the same list wait until returned guard is dropped.
+    pub fn read_for<Type>(&self) -> ItemsGuard<T>
+    where
+        Type: 'static,
+    {
+        let inner = match self.get_list(TypeId::of::<Type>()) {
+            None => GuardInner::Sealed(&[]),
+            Some(list) => {
+                let items = list.items.read().unwrap();
+                match list.sealed.get() {
+                    Some(sealed) => GuardInner::Sealed(sealed),
+                    None => GuardInner::Locked(items),
+                }
+            }
+        };
+        ItemsGuard { inner }
+    }
+
+    fn list<Type>(&self) -> &'static List<T>
+    where
+        Type: 'static,
+    {
+        let id = TypeId::of::<Type>();
+        if let Some(list) = self.get_list(id) {
+            return list;
+        }
+        self.map.write().unwrap().entry(id).or_insert_with(|| {
+            memory::intentional_leak_new(List {
+                items: RwLock::new(Vec::new()),
+                sealed: OnceCell::new(),
+            })
+        })
+    }
+
+    fn get_list(&self, id: TypeId) -> Option<&'static List<T>> {
+        self.map.read().unwrap().get(&id).cloned()
+    }
+}
+
+impl<T: 'static> Default for StaticTypeVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Guard providing access to items of single type, see
+/// [`StaticTypeVec::read_for`].
+pub struct ItemsGuard<T: 'static> {
+    inner: GuardInner<T>,
+}
+
+enum GuardInner<T: 'static> {
+    Locked(RwLockReadGuard<'static, Vec<T>>),
+    Sealed(&'static [T]),
+}
+
+impl<T: 'static> Deref for ItemsGuard<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.inner {
+            GuardInner::Locked(items) => items,
+            GuardInner::Sealed(items) => items,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::thread;
+
+    #[test]
+    fn concurrent_pushes_are_kept() {
+        static LISTS: Lazy<StaticTypeVec<u32>> = Lazy::new(StaticTypeVec::new);
+
+        let threads: Vec<_> = (0..4)
+            .map(|i| thread::spawn(move || LISTS.push_for::<u8>(i).unwrap()))
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert!(LISTS.get_for::<u8>().is_none());
+        let mut sealed = LISTS.seal_for::<u8>().to_vec();
+        sealed.sort();
+        assert_eq!(sealed, [0, 1, 2, 3]);
+        assert_eq!(LISTS.read_for::<u8>().len(), 4);
+        assert!(LISTS.read_for::<u16>().is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use std::env;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::{Mutex, PoisonError};
+
+use crate::Error;
+
+/// Marker, whose address tells copies of the crate apart.
+static MARKER: u8 = 0;
+
+/// Claim of the process by this copy of the crate, while any
+/// `InstanceClaim` is alive.
+static CLAIM: Mutex<Option<Claim>> = Mutex::new(None);
+
+struct Claim {
+    /// Locked lock file, or `None` if it could not be locked.
+    file: Option<File>,
+    /// Number of alive `InstanceClaim`s.
+    holders: usize,
+}
+
+/// Claim of the process returned by [`check_unique_instance`]. Once every
+/// claim of this copy is dropped, lock file is unlocked and removed.
+#[derive(Debug)]
+#[must_use = "process is only claimed until the claim is dropped"]
+pub struct InstanceClaim {
+    _private: (),
+}
+
+impl Drop for InstanceClaim {
+    fn drop(&mut self) {
+        let mut claim = CLAIM.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(state) = claim.as_mut() {
+            state.holders -= 1;
+            if state.holders == 0 {
+                if state.file.is_some() {
+                    let _ = fs::remove_file(path());
+                }
+                *claim = None;
+            }
+        }
+    }
+}
+
+/// Check that no other copy of this crate was checked in the process.
+/// Requires `instance-check` feature, which requires Rust 1.89.
+///
+/// If two semver-incompatible versions of the crate are linked into one
+/// binary, each of them has its own maps, so per-type values are silently
+/// duplicated. Calling this function at startup from every copy, for
+/// example from crates which depend on different versions, detects it:
+/// first call claims the process until returned claim is dropped, and
+/// calls from other copies return [`Error::DuplicateCrate`].
+///
+/// Copies share no statics, so the claim is an exclusive lock of a file in
+/// temporary directory named after the process id. File is removed once
+/// claim is dropped. Lock is released by the OS when process exits, so
+/// child processes and files left by crashed processes are not mistaken
+/// for other copies. If lock file can not be locked, no copy is detected.
+///
+/// ```
+/// let _claim = generic_static::check_unique_instance().expect("generic_static is linked twice");
+/// ```
+pub fn check_unique_instance() -> Result<InstanceClaim, Error> {
+    let mut claim = CLAIM.lock().unwrap_or_else(PoisonError::into_inner);
+    match claim.as_mut() {
+        Some(state) => state.holders += 1,
+        None => {
+            *claim = Some(Claim {
+                file: lock()?,
+                holders: 1,
+            })
+        }
+    }
+    Ok(InstanceClaim { _private: () })
+}
+
+/// Lock file of current process.
+fn path() -> PathBuf {
+    env::temp_dir().join(format!("generic_static-{}.lock", process::id()))
+}
+
+/// Lock file of current process for this copy of the crate.
+fn lock() -> Result<Option<File>, Error> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path());
+    let mut file = match file {
+        Ok(file) => file,
+        Err(_) => return Ok(None),
+    };
+    match file.try_lock() {
+        Ok(()) => {
+            let _ = file.set_len(0);
+            let _ = file.write_all(instance().as_bytes());
+            Ok(Some(file))
+        }
+        Err(TryLockError::WouldBlock) => {
+            let mut other = String::new();
+            let _ = file.read_to_string(&mut other);
+            Err(Error::DuplicateCrate { other })
+        }
+        Err(TryLockError::Error(_)) => Ok(None),
+    }
+}
+
+/// Identity of this copy of the crate: version and address of `MARKER`.
+fn instance() -> String {
+    format!("{}@{:p}", env!("CARGO_PKG_VERSION"), &MARKER as *const u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn other_copy_can_not_claim_process() {
+        let first = check_unique_instance().unwrap();
+        let second = check_unique_instance().unwrap();
+
+        // Other copy opens the same file, but can not lock it
+        let err = lock().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "another copy of generic_static is linked into the process: {}",
+                instance()
+            )
+        );
+
+        drop(first);
+        assert!(path().exists());
+        drop(second);
+        assert!(!path().exists());
+        drop(check_unique_instance().unwrap());
+    }
+}
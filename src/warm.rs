@@ -0,0 +1,67 @@
+use crate::{ProvideValue, StaticTypeMap};
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Initialize value corresponding to provided type, without returning
+    /// it.
+    ///
+    /// Same as [`StaticTypeMap::call_once`], but intended for warmup at
+    /// startup, where only side effect of initialization is needed. Does
+    /// nothing if value is already initialized.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<String>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// fn name<T: 'static>() -> String {
+    ///     std::any::type_name::<T>().to_string()
+    /// }
+    ///
+    /// MAP.warm::<u8, _>(name::<u8>);
+    /// MAP.warm::<u16, _>(name::<u16>);
+    /// assert_eq!(MAP.try_initialized::<u8>().unwrap(), "u8");
+    /// ```
+    #[track_caller]
+    pub fn warm<Type, Init>(&'static self, f: Init)
+    where
+        Type: 'static,
+        Init: FnOnce() -> T,
+    {
+        self.call_once::<Type, _>(f);
+    }
+
+    /// Same as [`StaticTypeMap::warm`], but initializes value with
+    /// [`ProvideValue::provide`].
+    #[track_caller]
+    pub fn warm_default<Type>(&'static self)
+    where
+        Type: ProvideValue<T> + 'static,
+    {
+        self.call_once_default::<Type>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    struct A;
+    impl ProvideValue<u32> for A {
+        fn provide() -> u32 {
+            1
+        }
+    }
+
+    #[test]
+    fn warmed_values_are_initialized_once() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        MAP.warm_default::<A>();
+        MAP.warm::<A, _>(|| unreachable!());
+        MAP.warm::<u8, _>(|| 8);
+
+        assert_eq!(MAP.try_initialized::<A>(), Ok(&1));
+        assert_eq!(MAP.try_initialized::<u8>(), Ok(&8));
+    }
+}
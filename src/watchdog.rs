@@ -0,0 +1,168 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::OnceCell;
+
+use crate::{memory, StaticTypeMap};
+
+pub(crate) type WatchdogHook = Arc<dyn Fn(&'static str, Duration) + Send + Sync>;
+
+pub(crate) struct Watchdog {
+    threshold: Duration,
+    hook: WatchdogHook,
+}
+
+/// Initializer watched by watchdog thread.
+struct Watched {
+    id: u64,
+    type_name: &'static str,
+    start: Instant,
+    deadline: Instant,
+    hook: WatchdogHook,
+}
+
+/// Queue of watched initializers, shared by all maps and served by single
+/// thread.
+struct Watcher {
+    queue: Mutex<(u64, Vec<Watched>)>,
+    changed: Condvar,
+}
+
+/// `None` if watchdog thread could not be spawned.
+static WATCHER: OnceCell<Option<&'static Watcher>> = OnceCell::new();
+
+/// Stops watching initializer when dropped.
+pub(crate) struct WatchGuard {
+    watcher: &'static Watcher,
+    id: u64,
+}
+
+impl Drop for WatchGuard {
+    fn drop(&mut self) {
+        let mut queue = self.watcher.queue.lock().unwrap();
+        queue.1.retain(|watched| watched.id != self.id);
+    }
+}
+
+impl Watcher {
+    fn get() -> Option<&'static Watcher> {
+        *WATCHER.get_or_init(|| {
+            let watcher: &'static Watcher = memory::intentional_leak_new(Watcher {
+                queue: Mutex::new((0, Vec::new())),
+                changed: Condvar::new(),
+            });
+            thread::Builder::new()
+                .name("generic_static watchdog".to_string())
+                .spawn(move || watcher.run())
+                .ok()
+                .map(|_| watcher)
+        })
+    }
+
+    /// Call hooks of initializers, which passed their deadline.
+    fn run(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            let next = queue.1.iter().enumerate().min_by_key(|(_, watched)| watched.deadline);
+            queue = match next {
+                Some((index, watched)) if watched.deadline <= now => {
+                    let watched = queue.1.swap_remove(index);
+                    drop(queue);
+                    // Panicking hook must not stop watching of other initializers
+                    let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+                        (watched.hook)(watched.type_name, watched.start.elapsed())
+                    }));
+                    self.queue.lock().unwrap()
+                }
+                Some((_, watched)) => {
+                    let timeout = watched.deadline - now;
+                    self.changed.wait_timeout(queue, timeout).unwrap().0
+                }
+                None => self.changed.wait(queue).unwrap(),
+            };
+        }
+    }
+}
+
+impl<T: 'static, NS: 'static> StaticTypeMap<T, NS> {
+    /// Call `hook` with key type name and elapsed time, when initializer
+    /// runs longer than `threshold`.
+    ///
+    /// Initializers of all maps are watched by single thread, started on
+    /// first watched initialization, and hook is called on that thread
+    /// while initializer is still running, so slow hooks delay reports of
+    /// other maps. If the thread can not be spawned, initializers are not
+    /// watched. Replaces previously set watchdog.
+    ///
+    /// ```
+    /// use generic_static::StaticTypeMap;
+    /// use once_cell::sync::Lazy;
+    /// use std::time::Duration;
+    ///
+    /// static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    ///
+    /// MAP.set_watchdog(Duration::from_secs(5), |type_name, elapsed| {
+    ///     eprintln!("initializer of `{}` is running for {:?}", type_name, elapsed);
+    /// });
+    /// MAP.call_once::<u8, _>(|| 8);
+    /// ```
+    pub fn set_watchdog<Hook>(&self, threshold: Duration, hook: Hook)
+    where
+        Hook: Fn(&'static str, Duration) + Send + Sync + 'static,
+    {
+        *self.watchdog.write().unwrap() = Some(Watchdog {
+            threshold,
+            hook: Arc::new(hook),
+        });
+    }
+
+    /// Start watching initializer of provided type, if watchdog is set.
+    pub(crate) fn watch(&self, type_name: &'static str) -> Option<WatchGuard> {
+        let watchdog = self.watchdog.read().unwrap();
+        let watchdog = watchdog.as_ref()?;
+        let watcher = Watcher::get()?;
+        let start = Instant::now();
+        let mut queue = watcher.queue.lock().unwrap();
+        queue.0 += 1;
+        let id = queue.0;
+        queue.1.push(Watched {
+            id,
+            type_name,
+            start,
+            deadline: start + watchdog.threshold,
+            hook: watchdog.hook.clone(),
+        });
+        watcher.changed.notify_one();
+        Some(WatchGuard { watcher, id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn slow_initializer_is_reported() {
+        static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+        let (sender, reports) = channel();
+        let sender = Mutex::new(sender);
+        MAP.set_watchdog(Duration::from_millis(100), move |name, elapsed| {
+            let _ = sender.lock().unwrap().send((name, elapsed));
+        });
+
+        MAP.call_once::<u8, _>(|| 8);
+        // Initializer only finishes once watchdog reported it
+        MAP.call_once::<u16, _>(|| {
+            let (name, elapsed) = reports.recv().unwrap();
+            assert_eq!(name, "u16");
+            assert!(elapsed >= Duration::from_millis(100));
+            16
+        });
+        assert!(reports.try_recv().is_err());
+    }
+}
@@ -0,0 +1,78 @@
+//! Checks, that intentional leaks are allocated under frame matched by
+//! suppressions of `LeakReport`, by recording backtraces of allocations.
+//!
+//! Not run with `init-trace`, since capture of initialization backtrace
+//! would be recorded while backtrace lock is held.
+#![cfg(not(feature = "init-trace"))]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use generic_static::{StaticTypeInterner, StaticTypeMap};
+use once_cell::sync::Lazy;
+
+struct Recording;
+
+#[global_allocator]
+static ALLOCATOR: Recording = Recording;
+
+thread_local! {
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Addresses of allocations made while recording, with their backtraces.
+static ALLOCATIONS: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+
+unsafe impl GlobalAlloc for Recording {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        // Disable recording while capturing, since capture allocates too
+        if RECORDING.try_with(|on| on.replace(false)).unwrap_or(false) {
+            let trace = Backtrace::force_capture().to_string();
+            ALLOCATIONS.lock().unwrap().push((ptr as usize, trace));
+            RECORDING.with(|on| on.set(true));
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn record<R>(f: impl FnOnce() -> R) -> R {
+    RECORDING.with(|on| on.set(true));
+    let result = f();
+    RECORDING.with(|on| on.set(false));
+    result
+}
+
+/// Backtrace of recorded allocation at provided address.
+fn allocation_trace(address: usize) -> String {
+    let allocations = ALLOCATIONS.lock().unwrap();
+    let (_, trace) = allocations
+        .iter()
+        .rev()
+        .find(|(ptr, _)| *ptr == address)
+        .expect("allocation was not recorded");
+    trace.clone()
+}
+
+#[test]
+fn leaks_are_allocated_by_suppressed_frame() {
+    static MAP: Lazy<StaticTypeMap<u32>> = Lazy::new(StaticTypeMap::new);
+    let interner = StaticTypeInterner::new();
+
+    record(|| MAP.call_once::<u8, _>(|| 8));
+    let interned = record(|| interner.intern::<u8>("interned"));
+
+    let report = MAP.leak_report();
+    let suppression = report.lsan_suppressions();
+    let function = suppression.trim().trim_start_matches("leak:");
+    for address in [report.entries[0].address, interned.as_ptr() as usize] {
+        let trace = allocation_trace(address);
+        assert!(trace.contains(function), "not suppressed:\n{}", trace);
+    }
+}